@@ -0,0 +1,156 @@
+//! Numerical integration for `∫(` and differentiation for `d/dx(`. Like the
+//! elementary functions these work in `f64` and round the result back to 15
+//! significant digits.
+
+use crate::{Float, float};
+
+/// Tolerance used when `∫(` is given none.
+pub const DEFAULT_TOLERANCE: Float = float!((1) e -5);
+/// Tolerance used when `d/dx(` is given none.
+pub const DEFAULT_DERIVATIVE_TOLERANCE: Float = float!((1) e -10);
+
+/// Subintervals the integration may split into before giving up.
+const MAX_INTERVALS: usize = 2000;
+
+/// Abscissae of the 15-point Kronrod rule, the odd ones are shared with the
+/// 7-point Gauss rule.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6, 0.949_107_912_342_758_5, 0.864_864_423_359_769_1, 0.741_531_185_599_394_5,
+    0.586_087_235_467_691_1, 0.405_845_151_377_397_2, 0.207_784_955_007_898_48, 0.0,
+];
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_224, 0.063_092_092_629_978_56, 0.104_790_010_322_250_19, 0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9, 0.190_350_578_064_785_42, 0.204_432_940_075_298_89, 0.209_482_141_084_727_82,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7, 0.279_705_391_489_276_64, 0.381_830_050_505_118_9, 0.417_959_183_673_469_4,
+];
+
+/// Integrates `f` from `a` to `b` with adaptive Gauss–Kronrod quadrature.
+///
+/// Intervals are split until the estimated error is within `tolerance`.
+/// `None` when `f` is not defined on the interval or the integral does not
+/// converge.
+pub fn integrate(
+    mut f: impl FnMut(&Float) -> Option<Float>,
+    a: &Float,
+    b: &Float,
+    tolerance: &Float,
+) -> Option<Float> {
+    let mut f = |x: f64| f(&Float::from_f64(x)?).map(|y| y.to_f64());
+    let mut intervals = vec![(a.to_f64(), b.to_f64(), tolerance.to_f64().abs())];
+    let mut total = 0.0;
+    let mut splits = 0;
+
+    while let Some((a, b, tolerance)) = intervals.pop() {
+        let (kronrod, gauss) = gauss_kronrod(&mut f, a, b)?;
+        let mid = (a + b) / 2.0;
+
+        if (kronrod - gauss).abs() <= tolerance || mid == a || mid == b {
+            total += kronrod;
+            continue;
+        }
+
+        splits += 1;
+        if splits > MAX_INTERVALS {
+            return None;
+        }
+
+        intervals.push((a, mid, tolerance / 2.0));
+        intervals.push((mid, b, tolerance / 2.0));
+    }
+
+    Float::from_f64(total)
+}
+
+/// The 15-point Kronrod and embedded 7-point Gauss estimates over `[a, b]`.
+fn gauss_kronrod(f: &mut impl FnMut(f64) -> Option<f64>, a: f64, b: f64) -> Option<(f64, f64)> {
+    let centre = (a + b) / 2.0;
+    let half = (b - a) / 2.0;
+
+    let y = f(centre)?;
+    let mut kronrod = y * KRONROD_WEIGHTS[7];
+    let mut gauss = y * GAUSS_WEIGHTS[3];
+
+    for i in 0..7 {
+        let dx = half * KRONROD_NODES[i];
+        let y = f(centre - dx)? + f(centre + dx)?;
+        kronrod += y * KRONROD_WEIGHTS[i];
+        if i % 2 == 1 {
+            gauss += y * GAUSS_WEIGHTS[i / 2];
+        }
+    }
+
+    Some((kronrod * half, gauss * half))
+}
+
+/// Ratio between the steps of successive central differences.
+const STEP_RATIO: f64 = 1.4;
+/// Central differences taken before giving up.
+const DIFFERENCES: usize = 10;
+/// First step, relative to `x` away from zero.
+const FIRST_STEP: f64 = 1e-3;
+/// Times the first step is halved while it reaches outside the domain of `f`.
+const HALVINGS: usize = 40;
+
+/// Derivative of `f` at `x` from central differences with Richardson
+/// extrapolation.
+///
+/// `None` when `f` is not defined around `x` or the estimated error stays
+/// above `tolerance` relative to the derivative, as it does at points where
+/// `f` is not differentiable.
+pub fn differentiate(
+    mut f: impl FnMut(&Float) -> Option<Float>,
+    x: &Float,
+    tolerance: &Float,
+) -> Option<Float> {
+    let mut f = |x: f64| f(&Float::from_f64(x)?).map(|y| y.to_f64());
+    let x = x.to_f64();
+    let mut central = |h: f64| Some((f(x + h)? - f(x - h)?) / (2.0 * h));
+
+    // close to the edge of the domain the step has to be smaller than the
+    // distance to it
+    let mut h = FIRST_STEP * x.abs().max(1.0);
+    let mut first = central(h);
+    for _ in 0..HALVINGS {
+        if first.is_some() {
+            break;
+        }
+        h /= 2.0;
+        first = central(h);
+    }
+
+    // Ridders' scheme, each row extrapolates the previous one to a zero step
+    let mut table = [[0.0; DIFFERENCES]; DIFFERENCES];
+    table[0][0] = first?;
+    let mut best = table[0][0];
+    let mut error = f64::INFINITY;
+
+    for i in 1..DIFFERENCES {
+        h /= STEP_RATIO;
+        table[0][i] = central(h)?;
+
+        let mut factor = STEP_RATIO * STEP_RATIO;
+        for j in 1..=i {
+            table[j][i] = table[j - 1][i].mul_add(factor, -table[j - 1][i - 1]) / (factor - 1.0);
+            factor *= STEP_RATIO * STEP_RATIO;
+
+            let e = (table[j][i] - table[j - 1][i]).abs().max((table[j][i] - table[j - 1][i - 1]).abs());
+            if e <= error {
+                error = e;
+                best = table[j][i];
+            }
+        }
+
+        // stop once higher orders only make it worse
+        if (table[i][i] - table[i - 1][i - 1]).abs() >= 2.0 * error {
+            break;
+        }
+    }
+
+    if error > tolerance.to_f64().abs() * best.abs().max(1.0) {
+        return None;
+    }
+
+    Float::from_f64(best)
+}
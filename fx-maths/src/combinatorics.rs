@@ -0,0 +1,116 @@
+//! `x!`, `nPr` and `nCr`. These are worked out exactly on integers and only
+//! rounded to 15 significant digits at the end.
+
+use crate::Float;
+
+/// Largest `n` accepted by `nPr` and `nCr`.
+const MAX_N: u64 = 9_999_999_999;
+/// Largest `x` accepted by `x!`, `70!` is past `9.99…e99`.
+const MAX_FACTORIAL: u64 = 69;
+
+/// Unsigned integer in base 10⁹ limbs, least significant first.
+struct Big(Vec<u64>);
+
+const BASE: u64 = 1_000_000_000;
+/// Limbs past which the value cannot fit in a float anymore.
+const MAX_LIMBS: usize = 12;
+
+impl Big {
+    fn one() -> Self {
+        Self(vec![1])
+    }
+
+    fn mul(&mut self, n: u64) {
+        let mut carry = 0_u128;
+        for l in &mut self.0 {
+            let v = *l as u128 * n as u128 + carry;
+            *l = (v % BASE as u128) as u64;
+            carry = v / BASE as u128;
+        }
+
+        while carry != 0 {
+            self.0.push((carry % BASE as u128) as u64);
+            carry /= BASE as u128;
+        }
+    }
+
+    fn div(&mut self, n: u64) {
+        let mut rem = 0_u128;
+        for l in self.0.iter_mut().rev() {
+            let v = rem * BASE as u128 + *l as u128;
+            *l = (v / n as u128) as u64;
+            rem = v % n as u128;
+        }
+
+        while self.0.len() > 1 && self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+
+    const fn too_big(&self) -> bool {
+        self.0.len() > MAX_LIMBS
+    }
+
+    fn to_float(&self) -> Option<Float> {
+        let mut limbs = self.0.iter().rev();
+        let mut s = limbs.next()?.to_string();
+        for l in limbs {
+            s += &format!("{l:09}");
+        }
+
+        s.parse().ok()
+    }
+}
+
+/// The value as a natural number, `None` for negatives and non-integers.
+fn natural(x: &Float) -> Option<u64> {
+    u64::try_from(x.to_i64()?).ok()
+}
+
+impl Float {
+    pub fn factorial(&self) -> Option<Self> {
+        let n = natural(self).filter(|n| *n <= MAX_FACTORIAL)?;
+
+        let mut f = Big::one();
+        for i in 2..=n {
+            f.mul(i);
+        }
+
+        f.to_float()
+    }
+
+    /// `nPr`, the number of ways to arrange `r` out of `self` items.
+    pub fn permutations(&self, r: &Self) -> Option<Self> {
+        let n = natural(self).filter(|n| *n <= MAX_N)?;
+        let r = natural(r).filter(|r| *r <= n)?;
+
+        let mut p = Big::one();
+        for i in n - r + 1..=n {
+            p.mul(i);
+            if p.too_big() {
+                return None;
+            }
+        }
+
+        p.to_float()
+    }
+
+    /// `nCr`, the number of ways to choose `r` out of `self` items.
+    pub fn combinations(&self, r: &Self) -> Option<Self> {
+        let n = natural(self).filter(|n| *n <= MAX_N)?;
+        let r = natural(r).filter(|r| *r <= n)?;
+        let r = r.min(n - r);
+
+        // every partial result is itself nCi, so the division is exact
+        let mut c = Big::one();
+        for i in 0..r {
+            c.mul(n - i);
+            c.div(i + 1);
+            if c.too_big() {
+                return None;
+            }
+        }
+
+        c.to_float()
+    }
+}
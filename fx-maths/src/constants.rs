@@ -0,0 +1,47 @@
+//! Scientific constants of the CONST menu, CODATA 2018 values in SI units.
+
+use crate::{Float, float};
+
+pub struct Constant {
+    /// Symbol shown in the menu and the input line.
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub value: Float,
+}
+
+pub const CONSTANTS: &[Constant] = &[
+    Constant { symbol: "mₚ", name: "Proton mass", value: float!((167262192369) e -38) },
+    Constant { symbol: "mₙ", name: "Neutron mass", value: float!((167492749804) e -38) },
+    Constant { symbol: "mₑ", name: "Electron mass", value: float!((91093837015) e -41) },
+    Constant { symbol: "mμ", name: "Muon mass", value: float!((1883531627) e -37) },
+    Constant { symbol: "a₀", name: "Bohr radius", value: float!((529177210903) e -22) },
+    Constant { symbol: "h", name: "Planck constant", value: float!((662607015) e -42) },
+    Constant { symbol: "μN", name: "Nuclear magneton", value: float!((50507837461) e -37) },
+    Constant { symbol: "μB", name: "Bohr magneton", value: float!((92740100783) e -34) },
+    Constant { symbol: "ħ", name: "Reduced Planck constant", value: float!((1054571817) e -43) },
+    Constant { symbol: "α", name: "Fine-structure constant", value: float!((72973525693) e -13) },
+    Constant { symbol: "rₑ", name: "Classical electron radius", value: float!((28179403262) e -25) },
+    Constant { symbol: "λc", name: "Compton wavelength", value: float!((242631023867) e -23) },
+    Constant { symbol: "γₚ", name: "Proton gyromagnetic ratio", value: float!((26752218744) e -2) },
+    Constant { symbol: "R∞", name: "Rydberg constant", value: float!((1097373156816) e -5) },
+    Constant { symbol: "u", name: "Atomic mass unit", value: float!((16605390666) e -37) },
+    Constant { symbol: "F", name: "Faraday constant", value: float!((9648533212331) e -8) },
+    Constant { symbol: "e", name: "Elementary charge", value: float!((1602176634) e -28) },
+    Constant { symbol: "Nᴀ", name: "Avogadro constant", value: float!((602214076) e 15) },
+    Constant { symbol: "k", name: "Boltzmann constant", value: float!((1380649) e -29) },
+    Constant { symbol: "Vm", name: "Molar volume of ideal gas", value: float!((2241396954) e -11) },
+    Constant { symbol: "R", name: "Molar gas constant", value: float!((831446261815324) e -14) },
+    Constant { symbol: "c₀", name: "Speed of light in vacuum", value: float!((299792458) e 0) },
+    Constant { symbol: "c₁", name: "First radiation constant", value: float!((3741771852) e -25) },
+    Constant { symbol: "c₂", name: "Second radiation constant", value: float!((1438776877) e -11) },
+    Constant { symbol: "σ", name: "Stefan–Boltzmann constant", value: float!((5670374419) e -17) },
+    Constant { symbol: "ε₀", name: "Electric constant", value: float!((88541878128) e -22) },
+    Constant { symbol: "μ₀", name: "Magnetic constant", value: float!((125663706212) e -17) },
+    Constant { symbol: "φ₀", name: "Magnetic flux quantum", value: float!((2067833848) e -24) },
+    Constant { symbol: "g", name: "Standard gravity", value: float!((980665) e -5) },
+    Constant { symbol: "G₀", name: "Conductance quantum", value: float!((7748091729) e -14) },
+    Constant { symbol: "Z₀", name: "Characteristic impedance of vacuum", value: float!((376730313668) e -9) },
+    Constant { symbol: "t", name: "Celsius temperature", value: float!((27315) e -2) },
+    Constant { symbol: "G", name: "Newtonian constant of gravitation", value: float!((66743) e -15) },
+    Constant { symbol: "atm", name: "Standard atmosphere", value: float!((101325) e 0) },
+];
@@ -0,0 +1,63 @@
+//! Metric conversions of the CONV menu. Each pair of units is related by
+//! `to = from × scale + offset` with exact factors, the offset only being
+//! used for temperatures.
+
+use crate::{Float, float};
+
+pub struct Conversion {
+    pub from: &'static str,
+    pub to: &'static str,
+    scale: Float,
+    offset: Float,
+    /// Goes from `to` back to `from`.
+    inverse: bool,
+}
+
+impl Conversion {
+    pub fn apply(&self, x: &Float) -> Option<Float> {
+        if self.inverse {
+            (x.clone() - self.offset.clone())? / self.scale.clone()
+        } else {
+            (x.clone() * self.scale.clone())? + self.offset.clone()
+        }
+    }
+}
+
+/// Both directions between `from` and `to = from × scale`.
+const fn pair(from: &'static str, to: &'static str, scale: Float) -> [Conversion; 2] {
+    affine(from, to, scale, Float::ZERO)
+}
+
+const fn affine(from: &'static str, to: &'static str, scale: Float, offset: Float) -> [Conversion; 2] {
+    [
+        Conversion { from, to, scale: copy(&scale), offset: copy(&offset), inverse: false },
+        Conversion { from: to, to: from, scale, offset, inverse: true },
+    ]
+}
+
+const fn copy(f: &Float) -> Float {
+    Float { mantissa: f.mantissa, exponent: f.exponent, sign: f.sign }
+}
+
+const PAIRS: &[[Conversion; 2]] = &[
+    pair("in", "cm", float!((254) e -2)),
+    pair("ft", "m", float!((3048) e -4)),
+    pair("yd", "m", float!((9144) e -4)),
+    pair("mile", "km", float!((1609344) e -6)),
+    pair("nmi", "km", float!((1852) e -3)),
+    pair("acre", "m²", float!((40468564224) e -7)),
+    pair("gal", "L", float!((3785411784) e -9)),
+    pair("pc", "km", float!((308567758149137) e -1)),
+    pair("m/s", "km/h", float!((36) e -1)),
+    pair("oz", "g", float!((28349523125) e -9)),
+    pair("lb", "kg", float!((45359237) e -8)),
+    pair("atm", "Pa", float!((101325) e 0)),
+    pair("mmHg", "Pa", float!((133322387415) e -9)),
+    pair("hp", "kW", float!((74569987158227) e -14)),
+    pair("psi", "kPa", float!((689475729316836) e -14)),
+    pair("kgf", "N", float!((980665) e -5)),
+    pair("cal", "J", float!((4184) e -3)),
+    affine("°C", "°F", float!((18) e -1), float!((32) e 0)),
+];
+
+pub const CONVERSIONS: &[Conversion] = PAIRS.as_flattened();
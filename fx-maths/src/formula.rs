@@ -0,0 +1,131 @@
+//! Built-in formula library of FMLA mode.
+
+use crate::{Float, float};
+
+pub struct Formula {
+    pub name: &'static str,
+    /// Name of the variable the formula solves for.
+    pub result: &'static str,
+    /// Names of the variables prompted for, in order.
+    pub variables: &'static [&'static str],
+    pub eval: fn(&[Float]) -> Option<Float>,
+}
+
+const G: Float = float!((667430) e -16);
+const R: Float = float!((8314462618) e -9);
+const EPSILON_0: Float = float!((88541878128) e -22);
+
+const fn n(i: i64) -> Float {
+    Float::from_int(i)
+}
+
+fn sq(x: &Float) -> Option<Float> {
+    x.clone() * x.clone()
+}
+
+pub const FORMULAS: &[Formula] = &[
+    Formula {
+        name: "Pythagoras",
+        result: "c",
+        variables: &["a", "b"],
+        eval: |v| (sq(&v[0])? + sq(&v[1])?)?.sqrt(),
+    },
+    Formula {
+        name: "Heron",
+        result: "S",
+        variables: &["a", "b", "c"],
+        eval: |v| {
+            let s = (((v[0].clone() + v[1].clone())? + v[2].clone())? / n(2))?;
+            let p = (s.clone() * (s.clone() - v[0].clone())?)?;
+            let p = (p * (s.clone() - v[1].clone())?)?;
+            (p * (s - v[2].clone())?)?.sqrt()
+        },
+    },
+    Formula {
+        name: "Circle area",
+        result: "S",
+        variables: &["r"],
+        eval: |v| Float::PI * sq(&v[0])?,
+    },
+    Formula {
+        name: "Sphere volume",
+        result: "V",
+        variables: &["r"],
+        eval: |v| ((n(4) * Float::PI)? * v[0].pow(&n(3))?)? / n(3),
+    },
+    Formula {
+        name: "Quadratic root",
+        result: "x",
+        variables: &["a", "b", "c"],
+        eval: |v| {
+            let d = (sq(&v[1])? - ((n(4) * v[0].clone())? * v[2].clone())?)?.sqrt()?;
+            (-v[1].clone() + d)? / (n(2) * v[0].clone())?
+        },
+    },
+    Formula {
+        name: "Uniform accel",
+        result: "v",
+        variables: &["v₀", "a", "t"],
+        eval: |v| v[0].clone() + (v[1].clone() * v[2].clone())?,
+    },
+    Formula {
+        name: "Displacement",
+        result: "s",
+        variables: &["v₀", "a", "t"],
+        eval: |v| {
+            let at2 = ((v[1].clone() * sq(&v[2])?)? / n(2))?;
+            (v[0].clone() * v[2].clone())? + at2
+        },
+    },
+    Formula {
+        name: "Kinetic energy",
+        result: "E",
+        variables: &["m", "v"],
+        eval: |v| (v[0].clone() * sq(&v[1])?)? / n(2),
+    },
+    Formula {
+        name: "Gravitation",
+        result: "F",
+        variables: &["m₁", "m₂", "r"],
+        eval: |v| ((G * v[0].clone())? * v[1].clone())? / sq(&v[2])?,
+    },
+    Formula {
+        name: "Pendulum",
+        result: "T",
+        variables: &["l", "g"],
+        eval: |v| (n(2) * Float::PI)? * (v[0].clone() / v[1].clone())?.sqrt()?,
+    },
+    Formula {
+        name: "Ohm's law",
+        result: "V",
+        variables: &["I", "R"],
+        eval: |v| v[0].clone() * v[1].clone(),
+    },
+    Formula {
+        name: "Electric power",
+        result: "P",
+        variables: &["V", "R"],
+        eval: |v| sq(&v[0])? / v[1].clone(),
+    },
+    Formula {
+        name: "Resonance",
+        result: "f",
+        variables: &["L", "C"],
+        eval: |v| ((n(2) * Float::PI)? * (v[0].clone() * v[1].clone())?.sqrt()?)?.recip(),
+    },
+    Formula {
+        name: "Ideal gas",
+        result: "P",
+        variables: &["n", "T", "V"],
+        eval: |v| ((v[0].clone() * R)? * v[1].clone())? / v[2].clone(),
+    },
+    Formula {
+        name: "Coulomb's law",
+        result: "F",
+        variables: &["q₁", "q₂", "r"],
+        eval: |v| {
+            let k = ((n(4) * Float::PI)? * EPSILON_0)?;
+            (v[0].clone() * v[1].clone())? / (k * sq(&v[2])?)?
+        },
+    },
+];
@@ -0,0 +1,72 @@
+//! Elementary functions. These go through `f64` and are rounded back to 15
+//! significant digits, which is good enough until they get their own decimal
+//! implementations.
+
+use crate::Float;
+
+impl Float {
+    fn via_f64(&self, f: impl FnOnce(f64) -> f64) -> Option<Self> {
+        Self::from_f64(f(self.to_f64()))
+    }
+
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.is_negative() {
+            return None;
+        }
+
+        self.via_f64(f64::sqrt)
+    }
+
+    pub fn ln(&self) -> Option<Self> {
+        if self.is_negative() || self.is_zero() {
+            return None;
+        }
+
+        self.via_f64(f64::ln)
+    }
+
+    pub fn log10(&self) -> Option<Self> {
+        if self.is_negative() || self.is_zero() {
+            return None;
+        }
+
+        self.via_f64(f64::log10)
+    }
+
+    pub fn exp(&self) -> Option<Self> {
+        self.via_f64(f64::exp)
+    }
+
+    /// `10^x`, exact for integer `x`.
+    pub fn ten_pow(&self) -> Option<Self> {
+        match self.to_i64() {
+            Some(e) if e.abs() <= 100 => Self::from_parts(false, 1, e as i32),
+            Some(_) if self.is_negative() => Some(Self::ZERO),
+            Some(_) => None,
+            None => self.via_f64(|x| 10_f64.powf(x)),
+        }
+    }
+
+    /// `self^rhs`, multiplying exactly for small integer exponents.
+    pub fn pow(&self, rhs: &Self) -> Option<Self> {
+        if let Some(n) = rhs.to_i64().filter(|n| n.abs() <= 64) {
+            let mut acc = Self::ONE;
+            for _ in 0..n.abs() {
+                acc = (acc * self.clone())?;
+            }
+
+            return if n < 0 { Self::ONE / acc } else { Some(acc) };
+        }
+
+        if self.is_zero() {
+            return if rhs.is_negative() { None } else { Some(Self::ZERO) };
+        }
+
+        let base = self.to_f64();
+        Self::from_f64(base.powf(rhs.to_f64()))
+    }
+
+    pub fn recip(&self) -> Option<Self> {
+        Self::ONE / self.clone()
+    }
+}
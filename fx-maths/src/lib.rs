@@ -12,8 +12,21 @@
 )]
 
 use std::ops::*;
+use std::cmp::Ordering;
+use std::str::FromStr;
 use std::fmt::{self, Display, Formatter};
 
+mod combinatorics;
+mod functions;
+pub mod calculus;
+pub mod constants;
+pub mod conversion;
+pub mod formula;
+pub mod random;
+pub mod solve;
+pub mod stat;
+pub mod trig;
+
 #[cfg(test)]
 mod tests;
 
@@ -30,14 +43,14 @@ pub struct Digit(u8);
 #[macro_export]
 macro_rules! float {
     (($m: expr) e $e: expr) => {
-        Float {
+        $crate::Float {
             mantissa: $crate::to_digits($m),
             exponent: $e,
             sign: false
         }
     };
     (- ($m: expr) e $e: expr) => {
-        Float {
+        $crate::Float {
             mantissa: $crate::to_digits($m),
             exponent: $e,
             sign: true
@@ -62,6 +75,300 @@ pub fn from_digits(d: [Digit; 15]) -> u64 {
     d.into_iter().fold(0, |a, e| a * 10 + e.0 as u64)
 }
 
+impl Float {
+    pub const ZERO: Self = float!((0) e 0);
+    pub const ONE: Self = float!((1) e 0);
+    pub const PI: Self = float!((314159265358979) e -14);
+    pub const E: Self = float!((271828182845905) e -14);
+
+    /// Builds a normalized float from `±mantissa × 10^exponent`, rounding to
+    /// 15 significant digits. Returns `None` on overflow past `9.99…e99`,
+    /// underflow below `1e-99` flushes to zero like the hardware does.
+    pub const fn from_parts(sign: bool, mut mantissa: u128, mut exponent: i32) -> Option<Self> {
+        if mantissa == 0 {
+            return Some(Self::ZERO);
+        }
+
+        let digits = digit_count(mantissa);
+        if digits > 15 {
+            let div = 10_u128.pow(digits - 15);
+            let rem = mantissa % div;
+            mantissa /= div;
+            exponent += (digits - 15) as i32;
+
+            if rem * 2 >= div {
+                mantissa += 1;
+            }
+        }
+
+        while mantissa.is_multiple_of(10) {
+            mantissa /= 10;
+            exponent += 1;
+        }
+
+        let sci = exponent + digit_count(mantissa) as i32 - 1;
+        if sci > 99 {
+            return None;
+        } else if sci < -99 {
+            return Some(Self::ZERO);
+        }
+
+        Some(Self {
+            mantissa: to_digits(mantissa as u64),
+            exponent: exponent as i8,
+            sign,
+        })
+    }
+
+    pub const fn from_int(i: i64) -> Self {
+        Self::from_parts(i < 0, i.unsigned_abs() as u128, 0).unwrap()
+    }
+
+    /// Rounds an `f64` to 15 significant digits. `None` on NaN, infinities
+    /// and values out of the calculator's range.
+    pub fn from_f64(f: f64) -> Option<Self> {
+        if !f.is_finite() {
+            return None;
+        }
+
+        format!("{f:.14e}").parse().ok()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let m = self.mantissa_int() as f64;
+        let m = if self.exponent < 0 {
+            m / 10_f64.powi(-self.exponent as i32)
+        } else {
+            m * 10_f64.powi(self.exponent as i32)
+        };
+
+        if self.sign { -m } else { m }
+    }
+
+    /// Returns the value as an integer if it has no fractional part.
+    pub fn to_i64(&self) -> Option<i64> {
+        if !self.is_integer() || self.sci_exponent() > 17 {
+            return None;
+        }
+
+        let m = self.mantissa_int() as i64;
+        let m = if self.exponent < 0 {
+            m / 10_i64.pow(-self.exponent as u32)
+        } else {
+            m * 10_i64.pow(self.exponent as u32)
+        };
+        Some(if self.sign { -m } else { m })
+    }
+
+    pub fn mantissa_int(&self) -> u64 {
+        from_digits(self.mantissa)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa_int() == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.sign && !self.is_zero()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        if self.exponent >= 0 || self.is_zero() {
+            return true;
+        } else if self.exponent < -15 {
+            return false;
+        }
+
+        self.mantissa_int().is_multiple_of(10_u64.pow(-self.exponent as u32))
+    }
+
+    pub const fn abs(mut self) -> Self {
+        self.sign = false;
+        self
+    }
+
+    /// Exponent of the value written in scientific notation.
+    pub fn sci_exponent(&self) -> i32 {
+        self.exponent as i32 + digit_count(self.mantissa_int() as u128) as i32 - 1
+    }
+
+    /// Rounds to `digits` significant digits.
+    pub fn round_sig(&self, digits: u32) -> Self {
+        let m = self.mantissa_int() as u128;
+        let count = digit_count(m);
+        if m == 0 || count <= digits {
+            return self.clone();
+        }
+
+        let div = 10_u128.pow(count - digits);
+        let mut rounded = m / div;
+        if (m % div) * 2 >= div {
+            rounded += 1;
+        }
+
+        Self::from_parts(self.sign, rounded, self.exponent as i32 + (count - digits) as i32)
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Rounds to `places` digits after the decimal point.
+    pub fn round_places(&self, places: i32) -> Option<Self> {
+        let drop = -places - self.exponent as i32;
+        if drop <= 0 || self.is_zero() {
+            return Some(self.clone());
+        }
+        if drop > 15 {
+            return Some(Self::ZERO);
+        }
+
+        let m = self.mantissa_int() as u128;
+        let div = 10_u128.pow(drop as u32);
+        let mut rounded = m / div;
+        if (m % div) * 2 >= div {
+            rounded += 1;
+        }
+
+        Self::from_parts(self.sign, rounded, -places)
+    }
+
+    pub fn trunc(&self) -> Self {
+        self.round_toward_zero(0)
+    }
+
+    pub fn fract(&self) -> Option<Self> {
+        self.clone() - self.trunc()
+    }
+
+    fn round_toward_zero(&self, places: i32) -> Self {
+        let drop = -places - self.exponent as i32;
+        if drop <= 0 {
+            return self.clone();
+        }
+        if drop > 15 {
+            return Self::ZERO;
+        }
+
+        Self::from_parts(self.sign, self.mantissa_int() as u128 / 10_u128.pow(drop as u32), -places)
+            .unwrap_or(Self::ZERO)
+    }
+
+    /// Mantissa padded to exactly 15 digits, with the matching exponent.
+    fn widened(&self) -> (i128, i32) {
+        let m = self.mantissa_int() as i128;
+        if m == 0 {
+            return (0, 0);
+        }
+
+        let pad = 15 - digit_count(m as u128) as i32;
+        let m = m * 10_i128.pow(pad as u32);
+        (if self.sign { -m } else { m }, self.exponent as i32 - pad)
+    }
+
+    /// Formats the value the way the bottom line of the display does, using
+    /// at most `digits` significant digits and switching to scientific
+    /// notation outside of `10^-2 ≤ |x| < 10^digits`.
+    pub fn format(&self, digits: u32) -> String {
+        self.format_norm(digits, -2)
+    }
+
+    /// Like [`Self::format`], with scientific notation below
+    /// `10^min_exponent` instead. Norm 1 uses -2 and Norm 2 uses -9.
+    pub fn format_norm(&self, digits: u32, min_exponent: i32) -> String {
+        let r = self.round_sig(digits);
+        let sci = r.sci_exponent();
+        if r.is_zero() || (min_exponent..digits as i32).contains(&sci) {
+            r.format_fixed(None)
+        } else {
+            r.format_sci(digits)
+        }
+    }
+
+    /// Formats in plain decimal notation, optionally with a fixed number of
+    /// decimal places.
+    pub fn format_fixed(&self, places: Option<u32>) -> String {
+        let r = places
+            .and_then(|p| self.round_places(p as i32))
+            .unwrap_or_else(|| self.clone());
+        let digits = r.mantissa_int().to_string();
+        let exponent = r.exponent as i32;
+
+        let (mut int, mut frac) = if exponent >= 0 {
+            (format!("{digits}{}", "0".repeat(exponent as usize)), String::new())
+        } else {
+            let point = digits.len() as i32 + exponent;
+            if point > 0 {
+                (digits[..point as usize].to_string(), digits[point as usize..].to_string())
+            } else {
+                ("0".to_string(), format!("{}{digits}", "0".repeat(-point as usize)))
+            }
+        };
+
+        if r.is_zero() {
+            int = "0".to_string();
+            frac.clear();
+        }
+
+        if let Some(p) = places {
+            while frac.len() < p as usize {
+                frac.push('0');
+            }
+        }
+
+        let sign = if r.is_negative() { "-" } else { "" };
+        if frac.is_empty() {
+            format!("{sign}{int}")
+        } else {
+            format!("{sign}{int}.{frac}")
+        }
+    }
+
+    /// Formats in scientific notation with at most `digits` significant
+    /// digits.
+    pub fn format_sci(&self, digits: u32) -> String {
+        self.sci_padded(digits, false)
+    }
+
+    /// Formats in scientific notation with exactly `digits` significant
+    /// digits, padding with zeros as Sci mode does.
+    pub fn format_sci_fixed(&self, digits: u32) -> String {
+        self.sci_padded(digits, true)
+    }
+
+    fn sci_padded(&self, digits: u32, pad: bool) -> String {
+        let r = self.round_sig(digits);
+        let sci = if r.is_zero() { 0 } else { r.sci_exponent() };
+        let mut m = r.mantissa_int().to_string();
+        while pad && m.len() < digits as usize {
+            m.push('0');
+        }
+
+        let sign = if r.is_negative() { "-" } else { "" };
+        let (head, tail) = m.split_at(1);
+        let exp = superscript(sci);
+        if tail.is_empty() {
+            format!("{sign}{head}×10{exp}")
+        } else {
+            format!("{sign}{head}.{tail}×10{exp}")
+        }
+    }
+}
+
+fn superscript(e: i32) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    let mut s = if e < 0 { "⁻".to_string() } else { String::new() };
+    s.extend(e.unsigned_abs().to_string().bytes().map(|b| DIGITS[(b - b'0') as usize]));
+    s
+}
+
+const fn digit_count(mut m: u128) -> u32 {
+    let mut c = 0;
+    while m != 0 {
+        m /= 10;
+        c += 1;
+    }
+
+    c
+}
+
 impl Display for Float {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         if self.sign {
@@ -74,97 +381,133 @@ impl Display for Float {
     }
 }
 
-impl Add for Float {
-    type Output = Option<Self>;
-    fn add(self, rhs: Self) -> Option<Self> {
-        let exponent = self.exponent.min(rhs.exponent);
-        let lhs_sh = (self.exponent - exponent) as usize;
-        let rhs_sh = ( rhs.exponent - exponent) as usize;
-
-        let mut mantissa = [Digit(0); 15];
-        let mut carry = 0;
-        let mut reduced = 0;
-        let mut i = 15;
-        for (l, r) in shift(self.mantissa, lhs_sh).into_iter().zip(shift(rhs.mantissa, rhs_sh)).rev() {
-            let a = l.0 + r.0 + carry;
-            carry = a / 10;
-
-            if a % 10 != 0 || i != 15 {
-                i -= 1;
-                mantissa[i] = Digit(a % 10);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseFloatError;
+
+impl FromStr for Float {
+    type Err = ParseFloatError;
+
+    /// Parses `[-]digits[.digits][e[-]digits]`.
+    fn from_str(s: &str) -> Result<Self, ParseFloatError> {
+        let (sign, s) = s.strip_prefix('-').map_or((false, s), |s| (true, s));
+
+        let (m, e) = match s.split_once(['e', 'E']) {
+            Some((m, e)) => (m, e.parse::<i32>().map_err(|_| ParseFloatError)?),
+            None => (s, 0),
+        };
+
+        let (int, frac) = m.split_once('.').unwrap_or((m, ""));
+        if int.is_empty() && frac.is_empty() {
+            return Err(ParseFloatError);
+        }
+
+        let mut mantissa = 0_u128;
+        let mut exponent = e;
+        let mut significant = 0;
+        for (i, c) in int.chars().chain(frac.chars()).enumerate() {
+            let d = c.to_digit(10).ok_or(ParseFloatError)?;
+            if i >= int.len() {
+                exponent -= 1;
+            }
+
+            if significant < 30 {
+                mantissa = mantissa * 10 + d as u128;
+                if mantissa != 0 {
+                    significant += 1;
+                }
             } else {
-                reduced += 1;
+                exponent += 1;
             }
         }
 
-        while carry != 0 {
-            mantissa.rotate_right(1);
-            mantissa[0] = Digit(carry % 10);
-            carry /= 10;
-            reduced += 1;
+        Self::from_parts(sign, mantissa, exponent).ok_or(ParseFloatError)
+    }
+}
+
+impl Neg for Float {
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        if !self.is_zero() {
+            self.sign = !self.sign;
         }
 
-        Some(Self {
-            mantissa,
-            exponent: check_exponent(exponent.checked_add(reduced)?)?,
-            sign: self.sign ^ rhs.sign
-        })
+        self
     }
 }
 
-impl Mul for Float {
+impl Add for Float {
     type Output = Option<Self>;
-    fn mul(self, rhs: Self) -> Option<Self> {
-        let mut mantissa = [Digit(0); 15];
-        let mut carry = 0;
-        let mut reduced = 0;
-        let mut i = 15;
-        let l = from_digits(self.mantissa);
-        for r in rhs.mantissa.into_iter().rev() {
-            let a = l * r.0 as u64 + carry;
-            carry = a / 10;
-
-            if a % 10 != 0 || i != 15 {
-                i -= 1;
-                mantissa[i] = Digit((a % 10) as u8);
-            } else {
-                reduced += 1;
-            }
+    fn add(self, rhs: Self) -> Option<Self> {
+        let (l, le) = self.widened();
+        let (r, re) = rhs.widened();
+
+        if l == 0 {
+            return Some(rhs);
+        } else if r == 0 {
+            return Some(self);
         }
 
-        while carry != 0 {
-            mantissa.rotate_right(1);
-            mantissa[0] = Digit((carry % 10) as u8);
-            carry /= 10;
-            reduced += 1;
+        // too far apart to affect the 15 significant digits
+        if le - re > 16 {
+            return Some(self);
+        } else if re - le > 16 {
+            return Some(rhs);
         }
 
-        Some(Self {
-            mantissa,
-            exponent: check_exponent(check_exponent(self.exponent.checked_add(rhs.exponent)?)?.checked_add(reduced as i8)?)?,
-            sign: self.sign ^ rhs.sign
-        })
+        let exponent = le.min(re);
+        let sum = l * 10_i128.pow((le - exponent) as u32) + r * 10_i128.pow((re - exponent) as u32);
+
+        Self::from_parts(sum < 0, sum.unsigned_abs(), exponent)
     }
 }
 
-const fn check_exponent(e: i8) -> Option<i8> {
-    if e < -99 || e > 99 {
-        return None;
+impl Sub for Float {
+    type Output = Option<Self>;
+    fn sub(self, rhs: Self) -> Option<Self> {
+        self + -rhs
     }
-
-    Some(e)
 }
 
-fn shift(m: [Digit; 15], by: usize) -> [Digit; 15] {
-    let mut r = [Digit(0); 15];
+impl Mul for Float {
+    type Output = Option<Self>;
+    fn mul(self, rhs: Self) -> Option<Self> {
+        let product = self.mantissa_int() as u128 * rhs.mantissa_int() as u128;
 
-    for (i, d) in m.into_iter().enumerate() {
-        if let Some(i) = i.checked_sub(by) {
-            r[i] = d;
-        } else {
-            continue;
+        Self::from_parts(
+            self.sign ^ rhs.sign,
+            product,
+            self.exponent as i32 + rhs.exponent as i32
+        )
+    }
+}
+
+impl Div for Float {
+    type Output = Option<Self>;
+    fn div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
         }
+
+        let (l, le) = self.widened();
+        let (r, re) = rhs.widened();
+        let quotient = l.unsigned_abs() * 10_u128.pow(20) / r.unsigned_abs();
+
+        Self::from_parts(self.sign ^ rhs.sign, quotient, le - re - 20)
     }
+}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let (l, le) = self.widened();
+        let (r, re) = other.widened();
 
-    r
+        Some(match (l.signum(), r.signum()) {
+            (a, b) if a != b => a.cmp(&b),
+            (0, _) => Ordering::Equal,
+            (s, _) => {
+                let mag = le.cmp(&re).then(l.abs().cmp(&r.abs()));
+                if s < 0 { mag.reverse() } else { mag }
+            },
+        })
+    }
 }
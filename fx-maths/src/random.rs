@@ -0,0 +1,42 @@
+//! Random numbers for `Ran#` and `RanInt#(`.
+
+use crate::Float;
+
+/// A SplitMix64 generator, seeded explicitly so that results can be
+/// reproduced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// `Ran#`, a number from 0.000 to 0.999 in steps of 0.001.
+    pub const fn ran(&mut self) -> Float {
+        Float::from_parts(false, (self.next_u64() % 1000) as u128, -3).unwrap()
+    }
+
+    /// `RanInt#(a, b)`, an integer from `a` to `b`. `None` unless both are
+    /// integers with `a ≤ b`.
+    pub fn ran_int(&mut self, a: &Float, b: &Float) -> Option<Float> {
+        let (a, b) = (a.to_i64()?, b.to_i64()?);
+        if a > b {
+            return None;
+        }
+
+        let span = (b as i128 - a as i128 + 1) as u128;
+        let offset = (self.next_u64() as u128 % span) as i128;
+        Some(Float::from_int((a as i128 + offset) as i64))
+    }
+}
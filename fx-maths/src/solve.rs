@@ -0,0 +1,57 @@
+//! Newton's method as used by SOLVE.
+
+use crate::{Float, float};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solver {
+    /// Largest step, relative to the size of the root, accepted as converged.
+    pub tolerance: Float,
+    pub max_iterations: usize,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            tolerance: float!((1) e -13),
+            max_iterations: 150,
+        }
+    }
+}
+
+/// Relative step used for the numeric derivative.
+const DERIVATIVE_STEP: Float = float!((1) e -6);
+
+impl Solver {
+    /// Finds a root of `f` starting from `guess`. `f` returns `None` where it
+    /// is not defined; `None` is returned when there is no convergence within
+    /// the iteration cap, the derivative vanishes or `f` is not defined along
+    /// the way.
+    pub fn solve(&self, mut f: impl FnMut(&Float) -> Option<Float>, guess: Float) -> Option<Float> {
+        let mut x = guess;
+
+        for _ in 0..self.max_iterations {
+            let y = f(&x)?;
+            if y.is_zero() {
+                return Some(x);
+            }
+
+            let scale = if x.clone().abs() > Float::ONE { x.clone().abs() } else { Float::ONE };
+            let h = (scale.clone() * DERIVATIVE_STEP)?;
+            let ahead = f(&(x.clone() + h.clone())?)?;
+            let behind = f(&(x.clone() - h.clone())?)?;
+            let slope = ((ahead - behind)? / (h.clone() + h)?)?;
+            if slope.is_zero() {
+                return None;
+            }
+
+            let step = (y / slope)?;
+            x = (x - step.clone())?;
+
+            if step.abs() <= (scale * self.tolerance.clone())? {
+                return Some(x);
+            }
+        }
+
+        None
+    }
+}
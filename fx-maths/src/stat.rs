@@ -0,0 +1,183 @@
+//! Paired-variable regression as done in REG mode.
+
+use crate::Float;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regression {
+    /// `y = A + Bx`
+    Linear,
+    /// `y = A + B·ln x`
+    Logarithmic,
+    /// `y = A·e^(Bx)`
+    Exponential,
+    /// `y = A·B^x`
+    AbExponential,
+    /// `y = A·x^B`
+    Power,
+    /// `y = A + B/x`
+    Inverse,
+    /// `y = A + Bx + Cx²`
+    Quadratic,
+}
+
+/// A single data point with its frequency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Point {
+    pub x: Float,
+    pub y: Float,
+    pub freq: Float,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coefficients {
+    pub a: Float,
+    pub b: Float,
+    pub c: Float,
+    /// Correlation coefficient, not defined for quadratic regression.
+    pub r: Option<Float>,
+}
+
+/// Frequency-weighted sums over the data, after the model's transforms.
+#[derive(Debug, Clone)]
+struct Sums {
+    n: Float,
+    x: Float,
+    y: Float,
+    xx: Float,
+    yy: Float,
+    xy: Float,
+    xxx: Float,
+    xxy: Float,
+    xxxx: Float,
+}
+
+impl Regression {
+    fn transform(self, p: &Point) -> Option<(Float, Float)> {
+        use Regression::*;
+        Some(match self {
+            Linear | Quadratic => (p.x.clone(), p.y.clone()),
+            Logarithmic => (p.x.ln()?, p.y.clone()),
+            Exponential | AbExponential => (p.x.clone(), p.y.ln()?),
+            Power => (p.x.ln()?, p.y.ln()?),
+            Inverse => (p.x.recip()?, p.y.clone()),
+        })
+    }
+
+    fn sums(self, data: &[Point]) -> Option<Sums> {
+        let mut s = Sums {
+            n: Float::ZERO,
+            x: Float::ZERO,
+            y: Float::ZERO,
+            xx: Float::ZERO,
+            yy: Float::ZERO,
+            xy: Float::ZERO,
+            xxx: Float::ZERO,
+            xxy: Float::ZERO,
+            xxxx: Float::ZERO,
+        };
+
+        for p in data {
+            let (x, y) = self.transform(p)?;
+            let f = p.freq.clone();
+            let xx = (x.clone() * x.clone())?;
+
+            s.n = (s.n + f.clone())?;
+            s.x = (s.x + (x.clone() * f.clone())?)?;
+            s.y = (s.y + (y.clone() * f.clone())?)?;
+            s.xx = (s.xx + (xx.clone() * f.clone())?)?;
+            s.yy = (s.yy + ((y.clone() * y.clone())? * f.clone())?)?;
+            s.xy = (s.xy + ((x.clone() * y.clone())? * f.clone())?)?;
+            s.xxx = (s.xxx + ((xx.clone() * x)? * f.clone())?)?;
+            s.xxy = (s.xxy + ((xx.clone() * y)? * f.clone())?)?;
+            s.xxxx = (s.xxxx + ((xx.clone() * xx)? * f)?)?;
+        }
+
+        Some(s)
+    }
+
+    /// Fits the model to the data. `None` when the data is insufficient or a
+    /// transform is undefined for some point (e.g. `ln` of a negative `x`).
+    pub fn fit(self, data: &[Point]) -> Option<Coefficients> {
+        let s = self.sums(data)?;
+        if s.n.is_zero() {
+            return None;
+        }
+
+        // S(uv) = Σuv - ΣuΣv/n
+        let cov = |uv: &Float, u: &Float, v: &Float| uv.clone() - ((u.clone() * v.clone())? / s.n.clone())?;
+        let sxx = cov(&s.xx, &s.x, &s.x)?;
+        let syy = cov(&s.yy, &s.y, &s.y)?;
+        let sxy = cov(&s.xy, &s.x, &s.y)?;
+        let x_mean = (s.x.clone() / s.n.clone())?;
+        let y_mean = (s.y.clone() / s.n.clone())?;
+
+        if self == Self::Quadratic {
+            let sxx2 = cov(&s.xxx, &s.x, &s.xx)?;
+            let sx2x2 = cov(&s.xxxx, &s.xx, &s.xx)?;
+            let sx2y = cov(&s.xxy, &s.xx, &s.y)?;
+
+            let c = (((sxx.clone() * sx2y)? - (sxy.clone() * sxx2.clone())?)?
+                / ((sxx.clone() * sx2x2)? - (sxx2.clone() * sxx2.clone())?)?)?;
+            let b = ((sxy - (c.clone() * sxx2)?)? / sxx)?;
+            let a = ((y_mean - (b.clone() * x_mean)?)? - (c.clone() * (s.xx / s.n)?)?)?;
+
+            return Some(Coefficients { a, b, c, r: None });
+        }
+
+        let b = (sxy.clone() / sxx.clone())?;
+        let a = (y_mean - (b.clone() * x_mean)?)?;
+        let r = (sxy / (sxx * syy)?.sqrt()?)?;
+
+        let (a, b) = match self {
+            Self::Exponential | Self::Power => (a.exp()?, b),
+            Self::AbExponential => (a.exp()?, b.exp()?),
+            _ => (a, b),
+        };
+
+        Some(Coefficients { a, b, c: Float::ZERO, r: Some(r) })
+    }
+}
+
+impl Coefficients {
+    /// Estimated `y` for the given `x` (ŷ).
+    pub fn estimate_y(&self, model: Regression, x: &Float) -> Option<Float> {
+        use Regression::*;
+        let (a, b, c) = (self.a.clone(), self.b.clone(), self.c.clone());
+        match model {
+            Linear => a + (b * x.clone())?,
+            Logarithmic => a + (b * x.ln()?)?,
+            Exponential => a * (b * x.clone())?.exp()?,
+            AbExponential => a * b.pow(x)?,
+            Power => a * x.pow(&b)?,
+            Inverse => a + (b / x.clone())?,
+            Quadratic => (a + (b * x.clone())?)? + (c * (x.clone() * x.clone())?)?,
+        }
+    }
+
+    /// Estimated `x` for the given `y` (x̂). Quadratic regression has two
+    /// solutions, `x̂₁` and `x̂₂`; the others only return the first.
+    pub fn estimate_x(&self, model: Regression, y: &Float) -> Option<(Float, Option<Float>)> {
+        use Regression::*;
+        let (a, b, c) = (self.a.clone(), self.b.clone(), self.c.clone());
+        let single = match model {
+            Linear => ((y.clone() - a)? / b)?,
+            Logarithmic => ((y.clone() - a)? / b)?.exp()?,
+            Exponential => ((y.ln()? - a.ln()?)? / b)?,
+            AbExponential => ((y.ln()? - a.ln()?)? / b.ln()?)?,
+            Power => ((y.ln()? - a.ln()?)? / b)?.exp()?,
+            Inverse => (b / (y.clone() - a)?)?,
+            Quadratic => {
+                // x = (-B ± √(B² - 4C(A - y))) / 2C
+                let four_c = (Float::from_int(4) * c.clone())?;
+                let disc = ((b.clone() * b.clone())? - (four_c * (a - y.clone())?)?)?.sqrt()?;
+                let two_c = (Float::from_int(2) * c)?;
+                let x1 = ((-b.clone() + disc.clone())? / two_c.clone())?;
+                let x2 = ((-b - disc)? / two_c)?;
+
+                return Some((x1, Some(x2)));
+            },
+        };
+
+        Some((single, None))
+    }
+}
@@ -61,3 +61,242 @@ fn float_mul() {
     let b = float!((41111111111111) e -14);
     assert_eq!(a * b, Some(float!((123333333333333) e -14)));
 }
+
+#[test]
+fn float_sub_div() {
+    let a = float!((5) e 0);
+    let b = float!((8) e 0);
+    assert_eq!(a - b, Some(float!(-(3) e 0)));
+
+    let a = float!((1) e 0);
+    let b = float!((3) e 0);
+    assert_eq!(a / b, Some(float!((333333333333333) e -15)));
+
+    let a = float!((2) e 0);
+    assert_eq!(a / Float::ZERO, None);
+}
+
+#[test]
+fn float_normalize() {
+    assert_eq!(Float::from_parts(false, 1230, -1), Some(float!((123) e 0)));
+    assert_eq!(Float::from_parts(false, 1234567890123456, 0), Some(float!((123456789012346) e 1)));
+    assert_eq!(Float::from_parts(false, 1, 100), None);
+    assert_eq!(Float::from_parts(false, 1, -100), Some(Float::ZERO));
+}
+
+#[test]
+fn float_parse_format() {
+    assert_eq!("12.50".parse(), Ok(float!((125) e -1)));
+    assert_eq!("-0.003e2".parse(), Ok(float!(-(3) e -1)));
+    assert_eq!(float!((125) e -1).format(10), "12.5");
+    assert_eq!(float!((1) e 10).format(10), "1×10¹⁰");
+    assert_eq!(float!(-(15) e -4).format(10), "-1.5×10⁻³");
+    assert_eq!((Float::ONE / float!((3) e 0)).unwrap().format(10), "0.3333333333");
+    assert_eq!(float!((15) e -4).format_norm(10, -9), "0.0015");
+    assert_eq!(float!((15) e -11).format_norm(10, -9), "1.5×10⁻¹⁰");
+    assert_eq!(float!((125) e 0).format_sci_fixed(4), "1.250×10²");
+    assert_eq!(Float::ZERO.format_sci_fixed(3), "0.00×10⁰");
+    assert_eq!(float!((125) e -2).format_fixed(Some(3)), "1.250");
+}
+
+#[test]
+fn float_ordering() {
+    assert!(float!((5) e 0) > float!((49) e -1));
+    assert!(float!(-(5) e 0) < float!((1) e -3));
+    assert!(float!(-(5) e 0) < float!(-(4) e 0));
+}
+
+fn points(data: &[(f64, f64)]) -> Vec<stat::Point> {
+    data.iter().map(|&(x, y)| stat::Point {
+        x: Float::from_f64(x).unwrap(),
+        y: Float::from_f64(y).unwrap(),
+        freq: Float::ONE,
+    }).collect()
+}
+
+#[test]
+fn regression_linear() {
+    use stat::Regression;
+    let data = points(&[(10., 1003.), (15., 1005.), (20., 1010.), (25., 1011.), (30., 1014.)]);
+    let c = Regression::Linear.fit(&data).unwrap();
+    assert_eq!(c.a.format(10), "997.4");
+    assert_eq!(c.b.format(10), "0.56");
+    assert_eq!(c.r.as_ref().unwrap().format(10), "0.9826073689");
+    assert_eq!(c.estimate_y(Regression::Linear, &float!((18) e 0)).unwrap().format(10), "1007.48");
+}
+
+#[test]
+fn regression_quadratic() {
+    use stat::Regression;
+    let data = points(&[(29., 1.6), (50., 23.5), (74., 38.), (103., 46.4), (118., 48.9)]);
+    let c = Regression::Quadratic.fit(&data).unwrap();
+    assert_eq!(c.a.format(10), "-34.86377535");
+    assert_eq!(c.b.format(10), "1.466337699");
+    assert_eq!(c.c.format(10), "-6.467508931×10⁻³");
+    assert_eq!(c.r, None);
+}
+
+#[test]
+fn builtin_formulas() {
+    use formula::FORMULAS;
+    let find = |name| FORMULAS.iter().find(|f| f.name == name).unwrap();
+
+    let pythagoras = find("Pythagoras");
+    assert_eq!((pythagoras.eval)(&[float!((3) e 0), float!((4) e 0)]), Some(float!((5) e 0)));
+
+    let heron = find("Heron");
+    assert_eq!((heron.eval)(&[float!((3) e 0), float!((4) e 0), float!((5) e 0)]), Some(float!((6) e 0)));
+
+    let pendulum = find("Pendulum");
+    assert_eq!((pendulum.eval)(&[float!((1) e 0), float!((980665) e -5)]).unwrap().format(10), "2.006409293");
+}
+
+#[test]
+fn solve_newton() {
+    use solve::Solver;
+    let solver = Solver::default();
+
+    // x² − 2 = 0
+    let root = solver.solve(|x| (x.clone() * x.clone())? - float!((2) e 0), Float::ONE).unwrap();
+    assert_eq!(root.format(10), "1.414213562");
+
+    // x³ − 8 = 0 from a far guess
+    let root = solver.solve(|x| ((x.clone() * x.clone())? * x.clone())? - float!((8) e 0), float!((100) e 0)).unwrap();
+    assert_eq!(root.format(10), "2");
+
+    // x² + 1 has no real root
+    let diverging = Solver { max_iterations: 50, ..Solver::default() };
+    assert_eq!(diverging.solve(|x| (x.clone() * x.clone())? + Float::ONE, float!((3) e 0)), None);
+}
+
+#[test]
+fn integrate_gauss_kronrod() {
+    use calculus::{DEFAULT_TOLERANCE, integrate};
+
+    // ∫(X², 0, 3) = 9
+    let i = integrate(|x| x.clone() * x.clone(), &Float::ZERO, &float!((3) e 0), &DEFAULT_TOLERANCE).unwrap();
+    assert_eq!(i.format(10), "9");
+
+    // ∫(e^(X), 0, 1) = e − 1
+    let i = integrate(Float::exp, &Float::ZERO, &Float::ONE, &DEFAULT_TOLERANCE).unwrap();
+    assert_eq!(i.format(10), "1.718281828");
+
+    // reversed bounds flip the sign
+    let i = integrate(|x| x.recip(), &Float::E, &Float::ONE, &DEFAULT_TOLERANCE).unwrap();
+    assert_eq!(i.format(10), "-1");
+
+    // 1/X is not defined at 0
+    assert_eq!(integrate(|x| x.recip(), &-Float::ONE, &Float::ONE, &DEFAULT_TOLERANCE), None);
+}
+
+#[test]
+fn differentiate_richardson() {
+    use calculus::{DEFAULT_DERIVATIVE_TOLERANCE, differentiate};
+    let cube = |x: &Float| (x.clone() * x.clone())? * x.clone();
+
+    // d/dx(X³, 2) = 12
+    let d = differentiate(cube, &float!((2) e 0), &DEFAULT_DERIVATIVE_TOLERANCE).unwrap();
+    assert_eq!(d.format(10), "12");
+
+    // d/dx(e^(X), 1) = e
+    let d = differentiate(Float::exp, &Float::ONE, &DEFAULT_DERIVATIVE_TOLERANCE).unwrap();
+    assert_eq!(d.format(10), "2.718281828");
+
+    // d/dx(ln(X), 1000) = 0.001
+    let d = differentiate(Float::ln, &float!((1) e 3), &DEFAULT_DERIVATIVE_TOLERANCE).unwrap();
+    assert_eq!(d.format(10), "1×10⁻³");
+
+    // √(X) is not defined left of 0
+    assert_eq!(differentiate(Float::sqrt, &Float::ZERO, &DEFAULT_DERIVATIVE_TOLERANCE), None);
+
+    // d/dx(√(X), 0.05) = 2.236067977, the first step reaching past 0
+    let d = differentiate(Float::sqrt, &float!((5) e -2), &DEFAULT_DERIVATIVE_TOLERANCE).unwrap();
+    assert_eq!(d.format(9), "2.23606798");
+    let d = differentiate(Float::sqrt, &float!((1) e -4), &DEFAULT_DERIVATIVE_TOLERANCE).unwrap();
+    assert_eq!(d.format(10), "50");
+}
+
+#[test]
+fn combinatorics() {
+    let n = |i| Float::from_int(i);
+
+    assert_eq!(n(0).factorial(), Some(Float::ONE));
+    assert_eq!(n(10).factorial(), Some(n(3628800)));
+    assert_eq!(n(69).factorial().unwrap().format(15), "1.71122452428141×10⁹⁸");
+    assert_eq!(n(70).factorial(), None);
+    assert_eq!(n(-1).factorial(), None);
+    assert_eq!(float!((35) e -1).factorial(), None);
+
+    assert_eq!(n(10).permutations(&n(3)), Some(n(720)));
+    assert_eq!(n(10).combinations(&n(3)), Some(n(120)));
+    assert_eq!(n(100).combinations(&n(50)).unwrap().format(15), "1.00891344545564×10²⁹");
+    assert_eq!(n(5).combinations(&n(7)), None);
+    assert_eq!(n(5).permutations(&float!((15) e -1)), None);
+    assert_eq!(n(1000).permutations(&n(1000)), None);
+}
+
+#[test]
+fn random_seeded() {
+    use random::Rng;
+
+    let mut a = Rng::new(50);
+    let mut b = Rng::new(50);
+    for _ in 0..100 {
+        let r = a.ran();
+        assert_eq!(r, b.ran());
+        assert!(r >= Float::ZERO && r < Float::ONE);
+        assert!(r.round_places(3) == Some(r.clone()));
+
+        let i = a.ran_int(&Float::from_int(-3), &Float::from_int(3)).unwrap();
+        assert_eq!(Some(i.clone()), b.ran_int(&Float::from_int(-3), &Float::from_int(3)));
+        assert!(i >= Float::from_int(-3) && i <= Float::from_int(3) && i.is_integer());
+    }
+
+    assert_eq!(a.ran_int(&Float::ONE, &Float::ZERO), None);
+}
+
+#[test]
+fn trig_units() {
+    use trig::AngleUnit::*;
+    let n = |i| Float::from_int(i);
+
+    assert_eq!(n(30).sin(Degree).unwrap().format(10), "0.5");
+    assert_eq!(n(90).cos(Degree), Some(Float::ZERO));
+    assert_eq!(n(-270).sin(Degree), Some(Float::ONE));
+    assert_eq!(n(200).cos(Gradian), Some(-Float::ONE));
+    assert_eq!((Float::PI / n(6)).unwrap().sin(Radian).unwrap().format(10), "0.5");
+
+    assert_eq!(Float::ONE.atan2(&Float::ONE, Degree), Some(n(45)));
+    assert_eq!(Float::ONE.atan2(&-Float::ONE, Gradian), Some(n(150)));
+    assert_eq!(Float::ZERO.atan2(&-Float::ONE, Radian), Some(Float::PI));
+    assert_eq!(Float::ZERO.atan2(&Float::ZERO, Degree), None);
+    assert_eq!(n(3).hypot(&n(-4)), Some(n(5)));
+}
+
+#[test]
+fn constants_normalized() {
+    use constants::CONSTANTS;
+
+    for c in CONSTANTS {
+        let normalized = Float::from_parts(c.value.sign, c.value.mantissa_int() as u128, c.value.exponent as i32);
+        assert_eq!(normalized.as_ref(), Some(&c.value), "{}", c.name);
+    }
+
+    let c0 = CONSTANTS.iter().find(|c| c.symbol == "c₀").unwrap();
+    assert_eq!(c0.value.format(10), "299792458");
+}
+
+#[test]
+fn unit_conversions() {
+    use conversion::CONVERSIONS;
+    let find = |from, to| CONVERSIONS.iter().find(|c| c.from == from && c.to == to).unwrap();
+
+    assert_eq!(find("in", "cm").apply(&Float::from_int(10)), Some(float!((254) e -1)));
+    assert_eq!(find("cm", "in").apply(&float!((254) e -1)), Some(Float::from_int(10)));
+    assert_eq!(find("lb", "kg").apply(&Float::ONE), Some(float!((45359237) e -8)));
+    assert_eq!(find("km/h", "m/s").apply(&Float::from_int(36)), Some(Float::from_int(10)));
+
+    assert_eq!(find("°C", "°F").apply(&Float::from_int(100)), Some(Float::from_int(212)));
+    assert_eq!(find("°F", "°C").apply(&Float::from_int(-40)), Some(Float::from_int(-40)));
+    assert_eq!(find("°F", "°C").apply(&Float::from_int(50)), Some(Float::from_int(10)));
+}
@@ -0,0 +1,79 @@
+//! Trigonometric functions in the angle unit set up on the calculator. Like
+//! the other elementary functions these go through `f64`, except that whole
+//! quarter turns in degrees and grads give exact results.
+
+use crate::Float;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AngleUnit {
+    Degree,
+    Radian,
+    Gradian,
+}
+
+impl AngleUnit {
+    /// A quarter turn in this unit, `None` for radians where it is not exact.
+    const fn quarter_turn(self) -> Option<i64> {
+        match self {
+            Self::Degree => Some(90),
+            Self::Radian => None,
+            Self::Gradian => Some(100),
+        }
+    }
+
+    fn to_radians(self, x: f64) -> f64 {
+        match self {
+            Self::Degree => x.to_radians(),
+            Self::Radian => x,
+            Self::Gradian => x * std::f64::consts::PI / 200.0,
+        }
+    }
+
+    fn of_radians(self, x: f64) -> f64 {
+        match self {
+            Self::Degree => x.to_degrees(),
+            Self::Radian => x,
+            Self::Gradian => x * 200.0 / std::f64::consts::PI,
+        }
+    }
+}
+
+impl Float {
+    /// The angle as a whole number of quarter turns, if it is one.
+    fn quarter_turns(&self, unit: AngleUnit) -> Option<i64> {
+        (self.clone() / Self::from_int(unit.quarter_turn()?))?.to_i64()
+    }
+
+    pub fn sin(&self, unit: AngleUnit) -> Option<Self> {
+        match self.quarter_turns(unit).map(|q| q.rem_euclid(4)) {
+            Some(0 | 2) => Some(Self::ZERO),
+            Some(1) => Some(Self::ONE),
+            Some(_) => Some(-Self::ONE),
+            None => Self::from_f64(unit.to_radians(self.to_f64()).sin()),
+        }
+    }
+
+    pub fn cos(&self, unit: AngleUnit) -> Option<Self> {
+        match self.quarter_turns(unit).map(|q| q.rem_euclid(4)) {
+            Some(1 | 3) => Some(Self::ZERO),
+            Some(0) => Some(Self::ONE),
+            Some(_) => Some(-Self::ONE),
+            None => Self::from_f64(unit.to_radians(self.to_f64()).cos()),
+        }
+    }
+
+    /// Distance of the point `(self, y)` from the origin.
+    pub fn hypot(&self, y: &Self) -> Option<Self> {
+        ((self.clone() * self.clone())? + (y.clone() * y.clone())?)?.sqrt()
+    }
+
+    /// Angle of the point `(x, self)` from the positive x axis, between a
+    /// negative and a positive half turn. `None` at the origin.
+    pub fn atan2(&self, x: &Self, unit: AngleUnit) -> Option<Self> {
+        if self.is_zero() && x.is_zero() {
+            return None;
+        }
+
+        Self::from_f64(unit.of_radians(self.to_f64().atan2(x.to_f64())))
+    }
+}
@@ -1,4 +1,29 @@
-use crossterm::style::*;
+use std::sync::LazyLock;
+
+use fx_maths::{Float, constants::CONSTANTS, conversion::CONVERSIONS, formula::FORMULAS, random::Rng, stat::{Point, Regression}, trig::AngleUnit};
+use calc::CalcRun;
+use formula::{FormulaEdit, FormulaRun, USER_FORMULAS, UserFormula};
+use layout::{INPUT_COLUMNS, InputLayout};
+use program::{PROGRAM_AREAS, Program, Run};
+pub use script::{KEY_NAMES, UnknownKey, parse_script};
+pub use state::LoadError;
+use setup::{ComplexFormat, DisplayFormat, FractionFormat, Settings};
+use solve::SolveRun;
+pub use variable::Var;
+use variable::VARIABLES;
+
+mod calc;
+mod eval;
+mod formula;
+mod layout;
+mod parser;
+mod program;
+mod script;
+mod setup;
+mod solve;
+mod stat;
+mod state;
+mod variable;
 
 #[derive(Debug)]
 pub struct Calculator {
@@ -7,37 +32,106 @@ pub struct Calculator {
 
     pending_key: Option<Key>,
     modifier_key: Option<KeyModifier>,
+    /// Keys of the last key press, more than one if it is bound to a
+    /// sequence.
+    pressed: Vec<Key>,
 
     cursor_at: usize,
     replace_mode: bool,
+    /// First token of the input shown on the top line.
+    scroll: usize,
+    /// Columns the input scrolls within.
+    input_columns: usize,
 
     user_input: Vec<Token>,
+    /// Set after EXE, the next input starts a new calculation.
+    fresh: bool,
+    label: Option<String>,
+    result: Option<Float>,
+    /// Names of the results of `Pol(` or `Rec(`, shown with X and Y.
+    coordinates: Option<(&'static str, &'static str)>,
+    error: Option<Error>,
+    answer: Float,
+    variables: [Float; VARIABLES],
+    rng: Rng,
+    settings: Settings,
+
+    stat_data: Vec<Point>,
+
+    programs: [Program; PROGRAM_AREAS],
+    /// Program area open in the editor.
+    editing: Option<usize>,
+    running: Option<Run>,
+
+    formula: Option<FormulaRun>,
+    user_formulas: Vec<UserFormula>,
+    formula_edit: Option<FormulaEdit>,
+    solving: Option<SolveRun>,
+    calculating: Option<CalcRun>,
+
+    /// Whether `persist` writes the state, off when the saved state is of a
+    /// newer version and has to be left as it is.
+    persisting: bool,
 }
 
-#[derive(Debug)]
-enum Mode {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    Syntax, Math, Go, CantSolve,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
     Computation,
     Complex,
     Base(Base),
     SingleStat,
-    PairedStat,
+    PairedStat(Regression),
     Program
 }
 
-#[derive(Debug)]
-enum Base {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
     Binary, Octal, Decimal, Hexadecimal
 }
 
 #[derive(Debug)]
 enum Menu {
-    ModeSelect
+    ModeSelect,
+    RegressionType,
+    StatVariables(Regression),
+    ProgramSlot,
+    ProgramMode(usize),
+    ProgramRegression(usize),
+    ProgramCommand,
+    ProgramRun,
+    /// Names of the user formulas, listed after the built-in ones.
+    Formula(Vec<String>),
+    Constants,
+    Conversions,
+    Setup,
+    /// Asks for the digits of the chosen display format.
+    SetupDigits(DisplayFormat),
 }
 
-type MenuItem = (&'static str, usize, usize);
+/// Constants listed on each page of the CONST menu.
+const CONSTANTS_PER_PAGE: usize = 4;
+/// Conversions listed on each page of the CONV menu, both directions of a
+/// pair of units.
+const CONVERSIONS_PER_PAGE: usize = 2;
 
-#[derive(Debug)]
-enum Key {
+/// Steps of a calculation, as on the calculator.
+const INPUT_STEPS: usize = 99;
+/// Steps left when the cursor turns into a block to warn of the limit.
+const FEW_STEPS: usize = 10;
+
+static CONVERSION_NAMES: LazyLock<Vec<String>> = LazyLock::new(|| {
+    CONVERSIONS.iter().map(|c| format!("{}▶{}", c.from, c.to)).collect()
+});
+
+type MenuItem<'a> = (&'a str, usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
     Shift, Alpha, Up, Right, Mode,
     Prog, Fmla, Left, Down, PowNegOne, Cubed,
     Fraction, SquareRoot, Squared, Power, Log, Ln,
@@ -53,6 +147,7 @@ enum KeyModifier {
     Shift, ShiftHyp, Alpha, Rcl, Sto, Hyp
 }
 
+#[derive(Debug)]
 pub struct DisplayBlock {
     text: String,
     bold: bool, italic: bool
@@ -86,7 +181,35 @@ macro_rules! display_block {
     };
 }
 
-type DisplayBlocks = Vec<DisplayBlock>;
+pub type DisplayBlocks = Vec<DisplayBlock>;
+
+/// What the display shows.
+#[derive(Debug)]
+pub struct Display {
+    /// The status line of indicators.
+    pub stat: String,
+    pub top: DisplayBlocks,
+    pub bot: String,
+    /// The column of the cursor on the top line, if it is shown.
+    pub cursor: Option<(usize, CursorShape)>,
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How the cursor is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorShape {
+    /// Inserting.
+    Bar,
+    /// Overwriting.
+    Underline,
+    /// Few steps are left.
+    Block,
+}
 
 impl Calculator {
     pub fn new() -> Self {
@@ -96,102 +219,163 @@ impl Calculator {
 
             pending_key: None,
             modifier_key: None,
+            pressed: Vec::new(),
 
             cursor_at: 0,
             replace_mode: false,
+            scroll: 0,
+            input_columns: INPUT_COLUMNS,
 
-            user_input: Vec::with_capacity(99),
+            user_input: Vec::with_capacity(INPUT_STEPS),
+            fresh: false,
+            label: None,
+            result: None,
+            coordinates: None,
+            error: None,
+            answer: Float::ZERO,
+            variables: [const { Float::ZERO }; VARIABLES],
+            rng: Rng::new(seed()),
+            settings: Settings::default(),
+
+            stat_data: Vec::new(),
+
+            programs: [const { Program::new() }; PROGRAM_AREAS],
+            editing: None,
+            running: None,
+
+            formula: None,
+            user_formulas: Vec::with_capacity(USER_FORMULAS),
+            formula_edit: None,
+            solving: None,
+            calculating: None,
+
+            persisting: true,
         }
     }
 
-    pub fn pretick(&mut self, ke: Option<crossterm::event::KeyEvent>) {
-        use crossterm::event::*;
+    /// A calculator whose `Ran#` and `RanInt#(` give the same numbers on
+    /// every run.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { rng: Rng::new(seed), ..Self::new() }
+    }
 
-        macro_rules! key_map {
-            ($($($og: pat_param)|+ => $k: expr), * $(,)?) => {
-                match ke {
-                    $(
-                        $(Some(KeyEvent { code: $og, kind: KeyEventKind::Press, .. }))|+ => {
-                            self.pending_key = Some($k)
-                        },
-                    )*
-                    _ => self.pending_key = None,
-                }
-            };
-        }
+    /// Presses a key directly, as when it is clicked.
+    pub fn press(&mut self, key: Key) {
+        self.pressed = vec![key];
+    }
 
-        key_map!(
-            KeyCode::Char('`') => Key::Shift,
-            KeyCode::Char('0') => Key::_0,
-            KeyCode::Char('1') => Key::_1,
-            KeyCode::Char('2') => Key::_2,
-            KeyCode::Char('3') => Key::_3,
-            KeyCode::Char('4') => Key::_4,
-            KeyCode::Char('5') => Key::_5,
-            KeyCode::Char('6') => Key::_6,
-            KeyCode::Char('7') => Key::_7,
-            KeyCode::Char('8') => Key::_8,
-            KeyCode::Char('9') => Key::_9,
-            KeyCode::Char('-') => Key::Subtract,
-            KeyCode::Char('=') => Key::Alpha,
-            KeyCode::Enter     => Key::Exe,
-            KeyCode::Backspace => Key::Del,
-
-            KeyCode::Char('q') => Key::Prog,
-            KeyCode::Char('w') => Key::Fmla,
-            KeyCode::Char('e') => Key::Exp,
-            KeyCode::Char('t') => Key::PowNegOne,
-            KeyCode::Char('y') => Key::Cubed,
-            KeyCode::Char('u') => Key::Rcl,
-            KeyCode::Char('i') => Key::Eng,
-            KeyCode::Char('o') => Key::BracketStart,
-            KeyCode::Char('p') => Key::BracketEnd,
-            KeyCode::Char(']') => Key::MPlus,
-            KeyCode::Char('\\') => Key::Mode,
-
-            KeyCode::Char('a') => Key::Fraction,
-            KeyCode::Char('s') => Key::SquareRoot,
-            KeyCode::Char('d') => Key::Squared,
-            KeyCode::Char('f') => Key::Power,
-            KeyCode::Char('g') => Key::Log,
-            KeyCode::Char('h') => Key::Ln,
-            KeyCode::Char('k') => Key::Ac,
-            KeyCode::Char('l') => Key::Add,
-            KeyCode::Char(';') => Key::Multiply,
-
-            KeyCode::Char('z') => Key::Negative,
-            KeyCode::Char('x') => Key::Base60,
-            KeyCode::Char('c') => Key::Hyp,
-            KeyCode::Char('v') => Key::Sin,
-            KeyCode::Char('b') => Key::Cos,
-            KeyCode::Char('n') => Key::Tan,
-            KeyCode::Char('m') => Key::Ans,
-            KeyCode::Char(',') => Key::Comma,
-            KeyCode::Char('.') => Key::Dot,
-            KeyCode::Char('/') => Key::Divide,
-
-            KeyCode::Left  => Key::Left,
-            KeyCode::Down  => Key::Down,
-            KeyCode::Up    => Key::Up,
-            KeyCode::Right => Key::Right,
-        );
+    /// Presses keys in order with the next tick, as a keyboard key bound to
+    /// a sequence does.
+    pub fn press_keys(&mut self, keys: &[Key]) {
+        self.pressed = keys.to_vec();
+    }
+
+    /// The key handled by the last tick.
+    pub const fn pressed_key(&self) -> Option<Key> {
+        self.pending_key
+    }
+
+    pub const fn shift_active(&self) -> bool {
+        matches!(self.modifier_key, Some(KeyModifier::Shift | KeyModifier::ShiftHyp | KeyModifier::Sto))
+    }
+
+    pub const fn alpha_active(&self) -> bool {
+        matches!(self.modifier_key, Some(KeyModifier::Alpha))
+    }
+
+    pub const fn hyp_active(&self) -> bool {
+        matches!(self.modifier_key, Some(KeyModifier::Hyp | KeyModifier::ShiftHyp))
+    }
+
+    /// RCL, or STO after SHIFT RCL.
+    pub const fn rcl_active(&self) -> bool {
+        matches!(self.modifier_key, Some(KeyModifier::Rcl | KeyModifier::Sto))
+    }
+
+    pub const fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The result of the last calculation, none while it is being typed.
+    pub const fn result(&self) -> Option<&Float> {
+        self.result.as_ref()
+    }
+
+    pub const fn answer(&self) -> &Float {
+        &self.answer
+    }
+
+    /// The error shown, until AC or an arrow key.
+    pub const fn error(&self) -> Option<Error> {
+        self.error
     }
 
     pub fn tick(&mut self) {
+        if self.pressed.is_empty() {
+            self.pending_key = None;
+            self.handle_key();
+        }
+
+        for k in std::mem::take(&mut self.pressed) {
+            self.pending_key = Some(k);
+            self.handle_key();
+        }
+
+        self.scroll = InputLayout::new(&self.user_input).window(self.scroll, self.cursor_at, self.input_columns).0;
+    }
+
+    fn handle_key(&mut self) {
         match (&self.modifier_key, &self.pending_key, &self.menu, &self.mode) {
-            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Mode), None, _) => self.modifier_key = None,
-            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Mode), Some(_), _) => self.modifier_key = None,
+            (_, Some(Key::Ac), _, _) if self.running.is_some() => self.stop_program(),
+            (None, Some(Key::Exe), None, _) if self.running.is_some() => self.resume_program(),
+            (_, Some(Key::Mode | Key::Prog), _, _) if self.running.is_some() => (),
+            (_, Some(_), _, _) if self.running.is_some() && !self.prompting() => (),
+
+            (_, Some(Key::Ac), None, _) if self.error.is_some() => {
+                self.error = None;
+                self.clear();
+            },
+            (_, Some(Key::Left | Key::Right), None, _) if self.error.is_some() => {
+                self.error = None;
+                self.modifier_key = None;
+            },
+            (_, Some(_), None, _) if self.error.is_some() => (),
+
+            (None, Some(Key::Exe), None, _) if self.formula.is_some() => self.formula_exe(),
+            (_, Some(Key::Ac), None, _) if self.formula.is_some() => self.exit_formula(),
+            (_, Some(k), None, _) if !matches!(k, Key::Mode | Key::Fmla | Key::Shift) && self.formula.as_ref().is_some_and(FormulaRun::showing_result) => (),
+
+            (None, Some(Key::Exe), None, _) if self.formula_edit.is_some() => self.formula_edit_exe(),
+            (_, Some(Key::Ac), None, _) if self.formula_edit.is_some() => self.cancel_formula_edit(),
+            (_, Some(Key::Fmla | Key::Prog), None, _) if self.formula_edit.is_some() => (),
+
+            (Some(KeyModifier::Shift), Some(Key::Fmla), None, _) if self.solving.is_some() => self.solve(),
+            (None, Some(Key::Exe), None, _) if self.solving.is_some() => self.solve_exe(),
+            (_, Some(Key::Ac), None, _) if self.solving.is_some() => self.exit_solve(),
+            (_, Some(Key::Fmla | Key::Prog), None, _) if self.solving.is_some() => self.modifier_key = None,
+            (_, Some(k), None, _) if !matches!(k, Key::Mode | Key::Shift) && self.solving.as_ref().is_some_and(SolveRun::showing_result) => (),
+
+            (None, Some(Key::Exe), None, _) if self.calculating.is_some() => self.calc_exe(),
+            (_, Some(Key::Ac), None, _) if self.calculating.is_some() => self.exit_calc(),
+            (_, Some(Key::Fmla | Key::Prog), None, _) if self.calculating.is_some() => self.modifier_key = None,
+            (_, Some(k), None, _) if !matches!(k, Key::Mode | Key::Shift) && self.calculating.as_ref().is_some_and(CalcRun::showing_result) => (),
+
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Mode), _, _) => self.setup(),
             (_, Some(Key::Mode), menu, _) => self.menu = match menu {
                 None => Some((Menu::ModeSelect, 0)),
                 Some((Menu::ModeSelect, 0)) => Some((Menu::ModeSelect, 1)),
                 Some((Menu::ModeSelect, 1)) => None,
-                _ => return,
+                _ => Some((Menu::ModeSelect, 0)),
             },
             (Some(KeyModifier::Shift), Some(Key::Shift), _, _) => self.modifier_key = None,
             (Some(KeyModifier::Hyp), Some(Key::Shift), _, _) => self.modifier_key = Some(KeyModifier::ShiftHyp),
             (Some(KeyModifier::ShiftHyp), Some(Key::Shift), _, _) => self.modifier_key = Some(KeyModifier::Hyp),
             (_, Some(Key::Shift), _, _) => self.modifier_key = Some(KeyModifier::Shift),
 
+            (Some(KeyModifier::Alpha), Some(k), None, _) if Var::from_key(k).is_some() => self.insert(Token::Variable(Var::from_key(k).unwrap())),
+            (Some(KeyModifier::Sto), Some(k), None, _) if Var::from_key(k).is_some() => self.store(Var::from_key(k).unwrap()),
+            (Some(KeyModifier::Rcl), Some(k), None, _) if Var::from_key(k).is_some() => self.recall(Var::from_key(k).unwrap()),
+
             (Some(KeyModifier::Hyp), Some(Key::Hyp), _, _) => self.modifier_key = None,
             (Some(KeyModifier::Shift), Some(Key::Hyp), _, _) => self.modifier_key = Some(KeyModifier::ShiftHyp),
             (Some(KeyModifier::ShiftHyp), Some(Key::Hyp), _, _) => self.modifier_key = Some(KeyModifier::Shift),
@@ -205,9 +389,10 @@ impl Calculator {
             (Some(KeyModifier::Rcl), Some(Key::Rcl), _, _) => self.modifier_key = None,
             (_, Some(Key::Rcl), _, _) => self.modifier_key = Some(KeyModifier::Rcl),
 
-            (_, Some(Key::Left) , Some((menu, page)), _) => self.menu.as_mut().unwrap().1 = page.checked_sub(1).unwrap_or(menu.pages()-1),
+            (_, Some(Key::Left) , Some((menu, page)), _) => self.menu.as_mut().unwrap().1 = page.checked_sub(1).unwrap_or_else(|| menu.pages()-1),
             (_, Some(Key::Right), Some((menu, page)), _) => self.menu.as_mut().unwrap().1 = (page+1) % menu.pages(),
 
+            (_, Some(Key::Left | Key::Right | Key::Up | Key::Down), None, _) if self.fresh => self.edit(),
             (_, Some(Key::Left) , None, _) => self.cursor_at = self.cursor_at.saturating_sub(1),
             (_, Some(Key::Right), None, _) => self.cursor_at = (self.cursor_at+1).min(self.user_input.len()),
             (_, Some(Key::Up)   , None, _) => self.cursor_at = 0,
@@ -215,6 +400,29 @@ impl Calculator {
 
             (None, Some(_), Some(_), _) => self.on_menu_interaction(),
 
+            (None, Some(Key::Prog), None, Mode::Program) => self.menu = Some((Menu::ProgramCommand, 0)),
+            (None, Some(Key::Exe), None, Mode::Program) => (),
+            (None, Some(Key::Ac), None, Mode::Program) => self.close_editor(),
+
+            (None, Some(Key::Prog), None, _) => self.menu = Some((Menu::ProgramRun, 0)),
+            (Some(KeyModifier::Shift), Some(Key::Prog), None, _) => self.calc(),
+            (None, Some(Key::Fmla), None, Mode::Program) => (),
+            (None, Some(Key::Fmla), None, _) => self.menu = self.formula_menu(0),
+            (Some(KeyModifier::Shift), Some(Key::Fmla), None, _) => self.solve(),
+            (Some(KeyModifier::Alpha), Some(Key::Fmla), None, _) => self.insert(Token::Equal),
+            (None, Some(Key::Exe), None, _) => self.execute(),
+            (None, Some(Key::Ac), None, _) => self.clear(),
+
+            (None, Some(Key::MPlus), None, Mode::PairedStat(_)) => self.enter_data(),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::MPlus), None, Mode::PairedStat(_)) => {
+                self.modifier_key = None;
+                self.clear_data();
+            },
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::_2), None, Mode::PairedStat(r)) => {
+                self.menu = Some((Menu::StatVariables(*r), 0));
+                self.modifier_key = None;
+            },
+
             (None, Some(Key::Power), None, _) => self.insert(Token::Power),
             (None, Some(Key::SquareRoot), None, _) => self.insert(Token::SquareRoot),
             (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Ln), None, _) => self.insert(Token::EPower),
@@ -236,31 +444,129 @@ impl Calculator {
             (None, Some(Key::Divide), None, _) => self.insert(Token::Divide),
             (None, Some(Key::Negative), None, _) => self.insert(Token::Negative),
             (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Log), None, _) => self.insert(Token::TenPower),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Squared), None, _) => self.insert(Token::Integral),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::SquareRoot), None, _) => self.insert(Token::Derivative),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::BracketStart), None, Mode::Computation) => self.insert(Token::Sum),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::BracketEnd), None, Mode::Computation) => self.insert(Token::Product),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::PowNegOne), None, _) => self.insert(Token::Factorial),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Multiply), None, _) => self.insert(Token::Permutations),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Divide), None, _) => self.insert(Token::Combinations),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Dot), None, _) => self.insert(Token::Random),
+            (Some(KeyModifier::Alpha), Some(Key::Dot), None, _) => self.insert(Token::RandomInt),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::_7), None, _) => {
+                self.menu = Some((Menu::Constants, 0));
+                self.modifier_key = None;
+            },
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::_8), None, _) => {
+                self.menu = Some((Menu::Conversions, 0));
+                self.modifier_key = None;
+            },
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Add), None, _) => self.insert(Token::Pol),
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Subtract), None, _) => self.insert(Token::Rec),
+            (None, Some(Key::Dot), None, _) => self.insert(Token::Dot),
+            (None, Some(Key::Exp), None, _) => self.insert(Token::Exp),
+            (None, Some(Key::BracketStart), None, _) => self.insert(Token::BracketStart),
+            (None, Some(Key::BracketEnd), None, _) => self.insert(Token::BracketEnd),
+            (None, Some(Key::Ans), None, _) => self.insert(Token::Ans),
+            (None, Some(Key::Comma), None, _) => self.insert(Token::Comma),
 
-            (None, Some(Key::Del), None, _) => {
-                self.cursor_at = self.cursor_at.saturating_sub(1);
-
-                if !self.user_input.is_empty() {
-                    self.user_input.remove(self.cursor_at);
-                }
+            (Some(KeyModifier::Shift | KeyModifier::ShiftHyp), Some(Key::Del), None, _) => {
+                self.replace_mode = !self.replace_mode;
+                self.modifier_key = None;
+            },
+            (None, Some(Key::Del), None, _) if self.fresh => self.edit(),
+            // overwriting deletes the token under the cursor, the one before
+            // it past the end
+            (None, Some(Key::Del), None, _) if self.replace_mode && self.cursor_at < self.user_input.len() => {
+                self.user_input.remove(self.cursor_at);
             },
+            (None, Some(Key::Del), None, _) if self.cursor_at > 0 => {
+                self.cursor_at -= 1;
+                self.user_input.remove(self.cursor_at);
+            },
+            // nothing before the cursor to delete
+            (None, Some(Key::Del), None, _) => (),
 
             (_, Some(_), _, _) => self.modifier_key = None,
             (_, None, _, _) => (),
         }
+
+        self.step_program();
+        self.sync_program();
     }
 
     fn insert(&mut self, t: Token) {
-        if !self.replace_mode {
-            self.user_input.insert(self.cursor_at, t);
-            self.cursor_at += 1;
-        } else {
+        if self.fresh {
+            self.clear();
+
+            if t.continues_answer() {
+                self.user_input.push(Token::Ans);
+                self.cursor_at = 1;
+            }
+        }
+
+        let overwriting = self.replace_mode && self.cursor_at < self.user_input.len();
+        if !overwriting && self.free_steps() == 0 {
+            return;
+        }
+
+        if overwriting {
             self.user_input[self.cursor_at] = t;
+        } else {
+            self.user_input.insert(self.cursor_at, t);
         }
+        self.cursor_at += 1;
         self.modifier_key = None;
     }
 
-    pub fn get_display(&self) -> (String, DisplayBlocks, String, Option<(usize, bool)>) {
+    fn insert_constant(&mut self, i: usize) {
+        if i < CONSTANTS.len() {
+            self.insert(Token::Constant(i));
+        }
+    }
+
+    /// Leaves the result screen to edit the last calculation.
+    fn edit(&mut self) {
+        self.fresh = false;
+        self.result = None;
+        self.label = None;
+        self.coordinates = None;
+        self.modifier_key = None;
+    }
+
+    fn clear(&mut self) {
+        self.user_input.clear();
+        self.cursor_at = 0;
+        self.edit();
+    }
+
+    fn execute(&mut self) {
+        if self.user_input.is_empty() {
+            return;
+        }
+
+        let parsed = parser::parse(&self.user_input);
+        let result = parsed.clone().and_then(|e| self.eval(&e));
+        match result {
+            Ok(r) => {
+                self.answer = r.clone();
+                self.result = Some(r);
+                self.label = None;
+                self.coordinates = parsed.ok().as_ref().and_then(parser::Expr::coordinates);
+                self.fresh = true;
+            },
+            Err(e) => self.error = Some(e),
+        }
+        self.modifier_key = None;
+    }
+
+    /// Sets the columns of the top line the input scrolls within, for a
+    /// display narrower than the text one.
+    pub fn set_input_columns(&mut self, columns: usize) {
+        self.input_columns = columns.max(1);
+    }
+
+    pub fn get_display(&self) -> Display {
         let mut stat = String::new();
         let mut top  = DisplayBlocks::new();
         let mut bot  = String::new();
@@ -268,11 +574,11 @@ impl Calculator {
 
         stat += &format!(
             // "SAhMSR CMPLX_SDREGPROG
-            //            r∠θR⇔I",
-            "{} {} r∠θR⇔I {:?}",
+            //            FIX_D_r∠θ",
+            "{} {} {}",
             self.modifier_key.as_ref().map_or("      ", |a| a.status_name()),
             self.mode.status_name(),
-            self.pending_key
+            self.settings.status_name(),
         );
 
         match &self.menu {
@@ -289,7 +595,10 @@ impl Calculator {
                 for i in items {
                     top.push(DisplayBlock { text: " ".repeat(i.2), bold: false, italic: false });
                     top.push(DisplayBlock { text: i.0.to_string(), bold: false, italic: false });
-                    bot += &i.1.to_string();
+                    // prompts have no number to press
+                    if i.1 != 0 {
+                        bot += &i.1.to_string();
+                    }
                     bot += &" ".repeat(16_usize.div_floor(items_len));
                 }
 
@@ -300,37 +609,97 @@ impl Calculator {
                     bold: false, italic: false
                 });
             },
+            None if self.error.is_some() => {
+                top.push(display_block!(" "));
+                top.push(display_block!(self.error.unwrap().name()));
+                bot += "  [AC]:Cancel";
+            },
+            None if self.fresh && self.coordinates.is_some() => {
+                let (first, second) = self.coordinates.unwrap();
+                let (x, y) = (self.settings.format(self.variable(Var::X)), self.settings.format(self.variable(Var::Y)));
+                top.push(display_block!(" "));
+                top.push(display_block!(format!("{first}={x}")));
+                bot += &format!("{second}={y:>15}");
+            },
+            None if self.user_input.is_empty() && self.label.is_some() => {
+                top.push(display_block!(" "));
+                top.push(display_block!(self.label.as_ref().unwrap()));
+                bot += &self.result_line();
+            },
             None => {
-                top.push(DisplayBlock {
-                    text: " ".to_string(),
-                    bold: false, italic: false
-                });
+                let layout = InputLayout::new(&self.user_input);
+                let (start, end) = layout.window(self.scroll, self.cursor_at, self.input_columns);
+                let shown = layout.span(start, end);
 
-                let mut cursor_acc = 0;
-                let mut cursor_position = 0;
-                for (i, el) in self.user_input.iter().enumerate() {
-                    let mut blocks = el.as_display_block();
-                    cursor_acc = blocks.iter().fold(cursor_acc, |acc, i| i.text.chars().count() + acc);
-                    top.append(&mut blocks);
+                top.push(display_block!(if start > 0 { "◀" } else { " " }));
+                for el in &self.user_input[start..end] {
+                    top.append(&mut el.as_display_block());
+                }
+                top.push(display_block!(" ".repeat(self.input_columns - shown)));
+                top.push(display_block!(if end < layout.len() { "▶" } else { " " }));
 
-                    if self.cursor_at-1 == i {
-                        cursor_position = cursor_acc
-                    }
+                match (self.editing, &self.formula_edit) {
+                    (Some(slot), _) => bot += &format!("  P{}{:>14}", slot + 1, self.remaining_steps()),
+                    (_, Some(edit)) if edit.naming() => bot += "  Name?",
+                    (_, Some(_)) => bot += "  Formula?",
+                    _ => bot += &self.result_line(),
+                }
+                if !self.fresh && (self.running.is_none() || self.prompting()) {
+                    let shape = if self.free_steps() <= FEW_STEPS {
+                        CursorShape::Block
+                    } else if self.replace_mode {
+                        CursorShape::Underline
+                    } else {
+                        CursorShape::Bar
+                    };
+                    cursor = Some((layout.span(start, self.cursor_at), shape));
                 }
-                bot += "            todo!";
-                cursor = Some((cursor_position, self.replace_mode));
             },
         }
 
-        (stat, top, bot, cursor)
+        Display { stat, top, bot, cursor }
+    }
+
+    /// Steps left for the input, in the program memory when editing a
+    /// program.
+    fn free_steps(&self) -> usize {
+        match self.editing {
+            Some(_) => self.remaining_steps(),
+            None => INPUT_STEPS.saturating_sub(self.user_input.len()),
+        }
+    }
+
+    /// The result right-aligned on the bottom line.
+    fn result_line(&self) -> String {
+        let Some(r) = &self.result else {
+            return String::new();
+        };
+
+        let r = self.settings.format(r);
+        format!("{}{r}", " ".repeat(17_usize.saturating_sub(r.chars().count())))
+    }
+
+    fn enter_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.editing = None;
+        self.formula = None;
+        self.formula_edit = None;
+        self.solving = None;
+        self.calculating = None;
+        self.clear();
+    }
+
+    fn enter_regression(&mut self, r: Regression) {
+        self.enter_mode(Mode::PairedStat(r));
+        self.stat_data.clear();
     }
 
-    pub fn on_menu_interaction(&mut self) {
+    fn on_menu_interaction(&mut self) {
         macro_rules! map_menu {
-            ($($menu: ident page $page: pat, key $($key:ident)|+ => $block: expr),* $(,)?) => {
+            ($($menu: ident $(($($arg: pat),*))? page $page: pat, key $($key:ident)|+ => $block: expr),* $(,)?) => {
                 match (&self.menu.as_ref().unwrap(), self.pending_key.as_ref().unwrap()) {
                     $(
-                        ((Menu::$menu, $page), $(Key::$key)|+) => { $block },
+                        ((Menu::$menu $(($($arg),*))?, $page), $(Key::$key)|+) => { $block },
                     )*
                     (_, Key::Ac) => (),
                     _ => return,
@@ -338,43 +707,111 @@ impl Calculator {
             };
         }
 
+        let mut next_menu = None;
         map_menu!(
-            ModeSelect page _, key _1 => {
-                self.mode = Mode::Computation;
-                self.user_input.clear();
-                self.cursor_at = 0;
-            },
-            ModeSelect page _, key _2 => {
-                self.mode = Mode::Complex;
-                self.user_input.clear();
-                self.cursor_at = 0;
-            },
-            ModeSelect page _, key _3 => {
-                self.mode = Mode::Base(Base::Decimal);
-                self.user_input.clear();
-                self.cursor_at = 0;
-            },
-            ModeSelect page _, key _4 => {
-                self.mode = Mode::SingleStat;
-                self.user_input.clear();
-                self.cursor_at = 0;
-            },
-            ModeSelect page _, key _5 => {
-                self.mode = Mode::PairedStat;
-                self.user_input.clear();
-                self.cursor_at = 0;
-            },
-            ModeSelect page _, key _6 => {
-                self.mode = Mode::Program;
-                self.user_input.clear();
-                self.cursor_at = 0;
-            },
+            ModeSelect page _, key _1 => self.enter_mode(Mode::Computation),
+            ModeSelect page _, key _2 => self.enter_mode(Mode::Complex),
+            ModeSelect page _, key _3 => self.enter_mode(Mode::Base(Base::Decimal)),
+            ModeSelect page _, key _4 => self.enter_mode(Mode::SingleStat),
+            ModeSelect page _, key _5 => next_menu = Some((Menu::RegressionType, 0)),
+            ModeSelect page _, key _6 => self.enter_mode(Mode::Program),
+
+            RegressionType page _, key _1 => self.enter_regression(Regression::Linear),
+            RegressionType page _, key _2 => self.enter_regression(Regression::Logarithmic),
+            RegressionType page _, key _3 => self.enter_regression(Regression::Exponential),
+            RegressionType page _, key _4 => self.enter_regression(Regression::Power),
+            RegressionType page _, key _5 => self.enter_regression(Regression::Inverse),
+            RegressionType page _, key _6 => self.enter_regression(Regression::Quadratic),
+            RegressionType page _, key _7 => self.enter_regression(Regression::AbExponential),
+
+            StatVariables(_) page 0, key _1 => self.insert(Token::StatA),
+            StatVariables(_) page 0, key _2 => self.insert(Token::StatB),
+            StatVariables(_) page 0, key _3 => self.insert(Token::StatC),
+            StatVariables(Regression::Quadratic) page 1, key _1 => self.insert(Token::XHat1),
+            StatVariables(Regression::Quadratic) page 1, key _2 => self.insert(Token::XHat2),
+            StatVariables(_) page 1, key _1 => self.insert(Token::StatR),
+            StatVariables(_) page 1, key _2 => self.insert(Token::XHat),
+            StatVariables(_) page 1, key _3 => self.insert(Token::YHat),
+
+            ProgramSlot page _, key _1 => next_menu = Some((Menu::ProgramMode(0), 0)),
+            ProgramSlot page _, key _2 => next_menu = Some((Menu::ProgramMode(1), 0)),
+            ProgramSlot page _, key _3 => next_menu = Some((Menu::ProgramMode(2), 0)),
+            ProgramSlot page _, key _4 => next_menu = Some((Menu::ProgramMode(3), 0)),
+
+            ProgramMode(slot) page _, key _1 => self.edit_program(*slot, Mode::Computation),
+            ProgramMode(slot) page _, key _2 => self.edit_program(*slot, Mode::Complex),
+            ProgramMode(slot) page _, key _3 => self.edit_program(*slot, Mode::Base(Base::Decimal)),
+            ProgramMode(slot) page _, key _4 => self.edit_program(*slot, Mode::SingleStat),
+            ProgramMode(slot) page _, key _5 => next_menu = Some((Menu::ProgramRegression(*slot), 0)),
+
+            ProgramRegression(slot) page _, key _1 => self.edit_program(*slot, Mode::PairedStat(Regression::Linear)),
+            ProgramRegression(slot) page _, key _2 => self.edit_program(*slot, Mode::PairedStat(Regression::Logarithmic)),
+            ProgramRegression(slot) page _, key _3 => self.edit_program(*slot, Mode::PairedStat(Regression::Exponential)),
+            ProgramRegression(slot) page _, key _4 => self.edit_program(*slot, Mode::PairedStat(Regression::Power)),
+            ProgramRegression(slot) page _, key _5 => self.edit_program(*slot, Mode::PairedStat(Regression::Inverse)),
+            ProgramRegression(slot) page _, key _6 => self.edit_program(*slot, Mode::PairedStat(Regression::Quadratic)),
+            ProgramRegression(slot) page _, key _7 => self.edit_program(*slot, Mode::PairedStat(Regression::AbExponential)),
+
+            ProgramCommand page 0, key _1 => self.insert(Token::Prompt),
+            ProgramCommand page 0, key _2 => self.insert(Token::Store),
+            ProgramCommand page 0, key _3 => self.insert(Token::Colon),
+            ProgramCommand page 0, key _4 => self.insert(Token::Display),
+            ProgramCommand page 1, key _1 => self.insert(Token::Then),
+            ProgramCommand page 1, key _2 => self.insert(Token::Label),
+            ProgramCommand page 1, key _3 => self.insert(Token::Goto),
+            ProgramCommand page 2, key _1 => self.insert(Token::Isz),
+            ProgramCommand page 2, key _2 => self.insert(Token::Dsz),
+            ProgramCommand page 3, key _1 => self.insert(Token::Equal),
+            ProgramCommand page 3, key _2 => self.insert(Token::NotEqual),
+            ProgramCommand page 3, key _3 => self.insert(Token::Greater),
+            ProgramCommand page 3, key _4 => self.insert(Token::GreaterEqual),
+            ProgramCommand page 4, key _1 => self.insert(Token::Less),
+            ProgramCommand page 4, key _2 => self.insert(Token::LessEqual),
+
+            ProgramRun page _, key _1 => self.run_program(0),
+            ProgramRun page _, key _2 => self.run_program(1),
+            ProgramRun page _, key _3 => self.run_program(2),
+            ProgramRun page _, key _4 => self.run_program(3),
+
+            Formula(_) page p, key _1 | Exe => next_menu = self.formula_action(*p, 1),
+            Formula(_) page p, key _2 => next_menu = self.formula_action(*p, 2),
+            Formula(_) page p, key _3 => next_menu = self.formula_action(*p, 3),
+
+            Constants page p, key _1 => self.insert_constant(*p * CONSTANTS_PER_PAGE),
+            Constants page p, key _2 => self.insert_constant(*p * CONSTANTS_PER_PAGE + 1),
+            Constants page p, key _3 => self.insert_constant(*p * CONSTANTS_PER_PAGE + 2),
+            Constants page p, key _4 => self.insert_constant(*p * CONSTANTS_PER_PAGE + 3),
+
+            Conversions page p, key _1 => self.insert(Token::Convert(*p * CONVERSIONS_PER_PAGE)),
+            Conversions page p, key _2 => self.insert(Token::Convert(*p * CONVERSIONS_PER_PAGE + 1)),
+
+            Setup page 0, key _1 => self.settings.angle = AngleUnit::Degree,
+            Setup page 0, key _2 => self.settings.angle = AngleUnit::Radian,
+            Setup page 0, key _3 => self.settings.angle = AngleUnit::Gradian,
+            Setup page 1, key _1 => next_menu = Some((Menu::SetupDigits(DisplayFormat::Fix(0)), 0)),
+            Setup page 1, key _2 => next_menu = Some((Menu::SetupDigits(DisplayFormat::Sci(0)), 0)),
+            Setup page 1, key _3 => next_menu = Some((Menu::SetupDigits(DisplayFormat::Norm(1)), 0)),
+            Setup page 2, key _1 => self.settings.fraction = FractionFormat::Mixed,
+            Setup page 2, key _2 => self.settings.fraction = FractionFormat::Improper,
+            Setup page 3, key _1 => self.settings.complex = ComplexFormat::Rectangular,
+            Setup page 3, key _2 => self.settings.complex = ComplexFormat::Polar,
+            Setup page 4, key _1 => self.settings.stat_frequency = true,
+            Setup page 4, key _2 => self.settings.stat_frequency = false,
+
+            SetupDigits(f) page _, key _0 | _1 | _2 | _3 | _4 | _5 | _6 | _7 | _8 | _9 => next_menu = self.set_display(*f),
         );
 
-        self.menu = None;
+        self.menu = next_menu;
     }
 }
 
+/// Seed for `Ran#`, different on every start.
+fn seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
 impl Mode {
     pub const fn status_name(&self) -> &'static str {
         use self::Base::*;
@@ -387,12 +824,34 @@ impl Mode {
             Base(Decimal)     => "     d",
             Base(Hexadecimal) => "     h",
             SingleStat        => "SD    ",
-            PairedStat        => "REG   ",
+            PairedStat(_)     => "REG   ",
             Program           => "PROG  ",
         }
     }
 }
 
+impl Error {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Syntax => "Syntax ERROR",
+            Self::Math => "Math ERROR",
+            Self::Go => "Go ERROR",
+            Self::CantSolve => "Can't Solve",
+        }
+    }
+}
+
+impl Key {
+    const fn digit(&self) -> Option<u8> {
+        use Key::*;
+        match self {
+            _0 => Some(0), _1 => Some(1), _2 => Some(2), _3 => Some(3), _4 => Some(4),
+            _5 => Some(5), _6 => Some(6), _7 => Some(7), _8 => Some(8), _9 => Some(9),
+            _ => None,
+        }
+    }
+}
+
 impl KeyModifier {
     pub const fn status_name(&self) -> &'static str {
         use KeyModifier::*;
@@ -413,35 +872,117 @@ impl Menu {
         use Menu::*;
         match self {
             ModeSelect => 2,
+            RegressionType | ProgramRegression(_) => 3,
+            StatVariables(_) => 2,
+            ProgramSlot | ProgramRun => 1,
+            ProgramMode(_) => 2,
+            ProgramCommand => 5,
+            Formula(user) => FORMULAS.len() + user.len() + (user.len() < USER_FORMULAS) as usize,
+            Constants => CONSTANTS.len().div_ceil(CONSTANTS_PER_PAGE),
+            Conversions => CONVERSIONS.len() / CONVERSIONS_PER_PAGE,
+            Setup => 5,
+            SetupDigits(_) => 1,
         }
     }
 
-    pub fn get_page(&self, page: usize) -> (Vec<MenuItem>, usize) {
+    pub fn get_page(&self, page: usize) -> (Vec<MenuItem<'_>>, usize) {
         use Menu::*;
         match (self, page) {
             (ModeSelect, 0) => (vec![("COMP", 1, 0), ("CMPLX", 2, 1), ("BASE", 3, 1)], 1),
             (ModeSelect, 1) => (vec![("SD", 4, 1), ("REG", 5, 2), ("PRGM", 6, 2)], 2),
 
+            (RegressionType | ProgramRegression(_), 0) => (vec![("Lin", 1, 1), ("Log", 2, 2), ("Exp", 3, 2)], 2),
+            (RegressionType | ProgramRegression(_), 1) => (vec![("Pwr", 4, 1), ("Inv", 5, 2), ("Quad", 6, 2)], 1),
+            (RegressionType | ProgramRegression(_), 2) => (vec![("AB-Exp", 7, 1)], 9),
+
+            (StatVariables(_), 0) => (vec![("A", 1, 2), ("B", 2, 4), ("C", 3, 4)], 3),
+            (StatVariables(Regression::Quadratic), 1) => (vec![("x̂₁", 1, 2), ("x̂₂", 2, 3), ("ŷ", 3, 3)], 3),
+            (StatVariables(_), 1) => (vec![("r", 1, 2), ("x̂", 2, 4), ("ŷ", 3, 4)], 3),
+
+            (ProgramSlot | ProgramRun, 0) => (vec![("P1", 1, 1), ("P2", 2, 2), ("P3", 3, 2), ("P4", 4, 2)], 3),
+            (ProgramMode(_), 0) => (vec![("COMP", 1, 0), ("CMPLX", 2, 1), ("BASE", 3, 1)], 1),
+            (ProgramMode(_), 1) => (vec![("SD", 4, 1), ("REG", 5, 2)], 6),
+            (ProgramCommand, 0) => (vec![("?", 1, 2), ("→", 2, 3), (":", 3, 3), ("◢", 4, 3)], 2),
+            (ProgramCommand, 1) => (vec![("⇒", 1, 1), ("Lbl", 2, 2), ("Goto", 3, 2)], 3),
+            (ProgramCommand, 2) => (vec![("Isz", 1, 2), ("Dsz", 2, 3)], 6),
+            (ProgramCommand, 3) => (vec![("=", 1, 2), ("≠", 2, 3), (">", 3, 3), ("≥", 4, 3)], 2),
+            (ProgramCommand, 4) => (vec![("<", 1, 2), ("≤", 2, 3)], 9),
+
+            (Formula(_), p) if p < FORMULAS.len() => {
+                let name = FORMULAS[p].name;
+                (vec![(name, 1, 1)], 14_usize.saturating_sub(name.chars().count()))
+            },
+            (Formula(user), p) if p - FORMULAS.len() < user.len() => {
+                let name = &user[p - FORMULAS.len()];
+                (vec![(name, 1, 1), ("Edt", 2, 2), ("Del", 3, 2)], 5_usize.saturating_sub(name.chars().count()))
+            },
+            (Formula(_), _) => (vec![("NEW", 1, 1)], 12),
+            (Constants, p) => {
+                let items: Vec<_> = CONSTANTS.iter()
+                    .skip(p * CONSTANTS_PER_PAGE)
+                    .take(CONSTANTS_PER_PAGE)
+                    .enumerate()
+                    .map(|(i, c)| (c.symbol, i + 1, if i == 0 { 1 } else { 2 }))
+                    .collect();
+                let width: usize = items.iter().map(|i| i.0.chars().count() + i.2).sum();
+                (items, 16_usize.saturating_sub(width))
+            },
+            (Conversions, p) => {
+                let (a, b) = (&CONVERSION_NAMES[p * CONVERSIONS_PER_PAGE], &CONVERSION_NAMES[p * CONVERSIONS_PER_PAGE + 1]);
+                (vec![(a, 1, 1), (b, 2, 2)], 12_usize.saturating_sub(a.chars().count() + b.chars().count()))
+            },
+
+            (Setup, 0) => (vec![("Deg", 1, 1), ("Rad", 2, 2), ("Gra", 3, 2)], 2),
+            (Setup, 1) => (vec![("Fix", 1, 1), ("Sci", 2, 2), ("Norm", 3, 2)], 1),
+            (Setup, 2) => (vec![("ab/c", 1, 1), ("d/c", 2, 2)], 6),
+            (Setup, 3) => (vec![("a+bi", 1, 1), ("r∠θ", 2, 2)], 6),
+            (Setup, 4) => (vec![("FreqOn", 1, 1), ("FreqOff", 2, 1)], 1),
+            (SetupDigits(f), 0) => {
+                let prompt = f.prompt();
+                (vec![(prompt, 0, 1)], 15_usize.saturating_sub(prompt.len()))
+            },
+
             _ => unreachable!()
         }
     }
 }
 
 impl DisplayBlock {
-    pub fn as_styled(&self) -> StyledContent<String> {
-        let mut c = self.text.clone().stylize();
-        if self.bold { c = c.bold(); }
-        if self.italic { c = c.italic(); }
-        c
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Columns taken on the display, combining marks going over the
+    /// character before them.
+    fn width(&self) -> usize {
+        self.text.chars().filter(|c| !matches!(c, '\u{0300}'..='\u{036f}')).count()
+    }
+
+    pub const fn bold(&self) -> bool {
+        self.bold
+    }
+
+    pub const fn italic(&self) -> bool {
+        self.italic
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum Token {
     Power, SquareRoot, EPower, E, Fraction,
     _0, _1, _2, _3, _4, _5, _6, _7, _8, _9,
     Add, Subtract, Multiply, Divide, Negative,
-    TenPower,
+    TenPower, Dot, Exp, BracketStart, BracketEnd, Ans, Comma,
+    StatA, StatB, StatC, StatR, XHat, XHat1, XHat2, YHat,
+    Colon, Display, Variable(Var), Store, Prompt, Label, Goto, Then, Isz, Dsz,
+    Equal, NotEqual, Greater, GreaterEqual, Less, LessEqual,
+    Integral, Derivative, Sum, Product,
+    Factorial, Permutations, Combinations, Random, RandomInt,
+    Pol, Rec,
+    /// Index into the constants catalogue.
+    Constant(usize),
+    /// Index into the conversions catalogue.
+    Convert(usize),
 }
 
 impl Token {
@@ -470,6 +1011,66 @@ impl Token {
             Divide          => vec![d!("÷")],
             Negative        => vec![d!("╶")],
             TenPower        => vec![d!("⒑^(")],
+            Dot             => vec![d!(".")],
+            Exp             => vec![d!("ᴇ")],
+            BracketStart    => vec![d!("(")],
+            BracketEnd      => vec![d!(")")],
+            Ans             => vec![d!("Ans")],
+            Comma           => vec![d!(",")],
+            StatA           => vec![d!(b "A")],
+            StatB           => vec![d!(b "B")],
+            StatC           => vec![d!(b "C")],
+            StatR           => vec![d!(b "r")],
+            XHat            => vec![d!(b "x̂")],
+            XHat1           => vec![d!(b "x̂₁")],
+            XHat2           => vec![d!(b "x̂₂")],
+            YHat            => vec![d!(b "ŷ")],
+            Colon           => vec![d!(":")],
+            Display         => vec![d!("◢")],
+            Variable(v)     => vec![d!(v.name())],
+            Store           => vec![d!("→")],
+            Prompt          => vec![d!("?")],
+            Label           => vec![d!("Lbl ")],
+            Goto            => vec![d!("Goto ")],
+            Then            => vec![d!("⇒")],
+            Isz             => vec![d!("Isz ")],
+            Dsz             => vec![d!("Dsz ")],
+            Equal           => vec![d!("=")],
+            NotEqual        => vec![d!("≠")],
+            Greater         => vec![d!(">")],
+            GreaterEqual    => vec![d!("≥")],
+            Less            => vec![d!("<")],
+            LessEqual       => vec![d!("≤")],
+            Integral        => vec![d!("∫(")],
+            Derivative      => vec![d!("d/dx(")],
+            Sum             => vec![d!("Σ(")],
+            Product         => vec![d!("Π(")],
+            Factorial       => vec![d!("!")],
+            Permutations    => vec![d!(b "P")],
+            Combinations    => vec![d!(b "C")],
+            Random          => vec![d!("Ran#")],
+            RandomInt       => vec![d!("RanInt#(")],
+            Pol             => vec![d!("Pol(")],
+            Rec             => vec![d!("Rec(")],
+            Constant(i)     => vec![d!(b i CONSTANTS[*i].symbol)],
+            Convert(i)      => vec![d!(CONVERSION_NAMES[*i])],
         }
     }
+
+    /// Columns taken on the display.
+    fn width(&self) -> usize {
+        self.as_display_block().iter().map(DisplayBlock::width).sum()
+    }
+
+    /// Whether typing this token right after a result continues the
+    /// calculation from `Ans`.
+    const fn continues_answer(&self) -> bool {
+        use Token::*;
+        matches!(
+            self,
+            Add | Subtract | Multiply | Divide | Power | Fraction
+                | XHat | XHat1 | XHat2 | YHat | Factorial | Permutations | Combinations
+                | Convert(_)
+        )
+    }
 }
@@ -0,0 +1,112 @@
+use super::{Calculator, Error, Token, parser};
+use super::parser::Expr;
+use super::variable::{Var, variables_in};
+
+/// An expression evaluated repeatedly with CALC, SHIFT PROG.
+#[derive(Debug)]
+pub struct CalcRun {
+    expression: Vec<Token>,
+    compiled: Expr,
+    vars: Vec<Var>,
+    /// Variable being prompted for, `None` while the result is shown.
+    at: Option<usize>,
+}
+
+impl CalcRun {
+    pub const fn showing_result(&self) -> bool {
+        self.at.is_none()
+    }
+}
+
+impl Calculator {
+    /// CALC, SHIFT PROG: starts prompting for the variables of the input.
+    pub(super) fn calc(&mut self) {
+        self.modifier_key = None;
+        if self.user_input.is_empty() || self.editing.is_some() || self.calculating.is_some() {
+            return;
+        }
+
+        let compiled = match parser::parse(&self.user_input) {
+            Ok(e) => e,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            },
+        };
+
+        self.calculating = Some(CalcRun {
+            expression: self.user_input.clone(),
+            compiled,
+            vars: variables_in(&self.user_input),
+            at: Some(0),
+        });
+        self.prompt_calc(0);
+    }
+
+    /// EXE in CALC: takes the value of the prompted variable, keeping the
+    /// previous one when nothing was typed, and evaluates once all are known.
+    /// EXE on the result goes through the prompts again.
+    pub(super) fn calc_exe(&mut self) {
+        let run = self.calculating.as_ref().unwrap();
+        let Some(at) = run.at else {
+            return self.prompt_calc(0);
+        };
+
+        if !self.user_input.is_empty() {
+            let v = run.vars[at];
+            match parser::parse(&self.user_input).and_then(|e| self.eval(&e)) {
+                Ok(value) => self.set_variable(v, value),
+                Err(e) => return self.calc_error(e),
+            }
+        }
+
+        self.prompt_calc(at + 1);
+    }
+
+    /// AC in CALC, back to the expression.
+    pub(super) fn exit_calc(&mut self) {
+        let Some(run) = self.calculating.take() else {
+            return;
+        };
+
+        self.clear();
+        self.user_input = run.expression;
+        self.cursor_at = self.user_input.len();
+    }
+
+    fn calc_error(&mut self, e: Error) {
+        self.exit_calc();
+        self.error = Some(e);
+    }
+
+    /// Prompts for a variable with its current value, or evaluates once all
+    /// are known.
+    fn prompt_calc(&mut self, at: usize) {
+        let run = self.calculating.as_mut().unwrap();
+        let Some(&v) = run.vars.get(at) else {
+            return self.evaluate_calc();
+        };
+
+        run.at = Some(at);
+        self.clear();
+        self.label = Some(format!("{}?", v.name()));
+        self.result = Some(self.variable(v).clone());
+    }
+
+    fn evaluate_calc(&mut self) {
+        let run = self.calculating.as_mut().unwrap();
+        run.at = None;
+
+        let compiled = run.compiled.clone();
+        match self.eval(&compiled) {
+            Ok(r) => {
+                self.clear();
+                self.user_input.clone_from(&self.calculating.as_ref().unwrap().expression);
+                self.answer = r.clone();
+                self.result = Some(r);
+                self.fresh = true;
+            },
+            Err(e) => self.calc_error(e),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use fx_maths::{Float, calculus, conversion::CONVERSIONS, stat::Regression};
+
+use super::{Calculator, Error, Mode};
+use super::parser::{BinaryOp, Expr, SeriesOp, StatVar, UnaryOp};
+use super::variable::Var;
+
+/// Most terms `Σ(` and `Π(` go through.
+const MAX_TERMS: i64 = 10_000;
+
+impl Calculator {
+    pub(super) fn eval(&mut self, e: &Expr) -> Result<Float, Error> {
+        use BinaryOp::*;
+        use UnaryOp::*;
+
+        Ok(match e {
+            Expr::Number(n) => n.clone(),
+            Expr::Ans => self.answer.clone(),
+            Expr::Variable(v) => self.variable(*v).clone(),
+            Expr::Stat(v) => {
+                let c = self.regression()?;
+                match v {
+                    StatVar::A => c.a,
+                    StatVar::B => c.b,
+                    StatVar::C => c.c,
+                    StatVar::R => c.r.ok_or(Error::Syntax)?,
+                }
+            },
+            Expr::Unary(op, a) => {
+                let a = self.eval(a)?;
+                match op {
+                    Negate => Some(-a),
+                    SquareRoot => a.sqrt(),
+                    EPower => a.exp(),
+                    TenPower => a.ten_pow(),
+                    Factorial => a.factorial(),
+                    XHat | XHat1 | XHat2 | YHat => return self.estimate(*op, &a),
+                }.ok_or(Error::Math)?
+            },
+            Expr::Binary(op, a, b) => {
+                let a = self.eval(a)?;
+                let b = self.eval(b)?;
+                match op {
+                    Add => a + b,
+                    Subtract => a - b,
+                    Multiply => a * b,
+                    Divide | Fraction => a / b,
+                    Power => a.pow(&b),
+                    Permutations => a.permutations(&b),
+                    Combinations => a.combinations(&b),
+                }.ok_or(Error::Math)?
+            },
+            Expr::Integral(f, a, b, tolerance) => {
+                let a = self.eval(a)?;
+                let b = self.eval(b)?;
+                let tolerance = match tolerance {
+                    Some(t) => self.eval(t)?,
+                    None => calculus::DEFAULT_TOLERANCE,
+                };
+
+                self.with_variable(Var::X, |c| calculus::integrate(|x| {
+                    c.set_variable(Var::X, x.clone());
+                    c.eval(f).ok()
+                }, &a, &b, &tolerance)).ok_or(Error::Math)?
+            },
+            Expr::Derivative(f, a, tolerance) => {
+                let a = self.eval(a)?;
+                let tolerance = match tolerance {
+                    Some(t) => self.eval(t)?,
+                    None => calculus::DEFAULT_DERIVATIVE_TOLERANCE,
+                };
+
+                self.with_variable(Var::X, |c| calculus::differentiate(|x| {
+                    c.set_variable(Var::X, x.clone());
+                    c.eval(f).ok()
+                }, &a, &tolerance)).ok_or(Error::Math)?
+            },
+            Expr::Series(op, f, v, a, b) => {
+                let a = self.eval(a)?;
+                let b = self.eval(b)?;
+                self.with_variable(*v, |c| c.series(*op, f, *v, &a, &b))?
+            },
+            Expr::Random => self.rng.ran(),
+            Expr::RandomInt(a, b) => {
+                let a = self.eval(a)?;
+                let b = self.eval(b)?;
+                self.rng.ran_int(&a, &b).ok_or(Error::Math)?
+            },
+            Expr::Polar(x, y) => {
+                let x = self.eval(x)?;
+                let y = self.eval(y)?;
+                let r = x.hypot(&y).ok_or(Error::Math)?;
+                let theta = y.atan2(&x, self.settings.angle).ok_or(Error::Math)?;
+                self.store_pair(r, theta)
+            },
+            Expr::Rectangular(r, theta) => {
+                let r = self.eval(r)?;
+                let theta = self.eval(theta)?;
+                let x = theta.cos(self.settings.angle).and_then(|c| r.clone() * c).ok_or(Error::Math)?;
+                let y = theta.sin(self.settings.angle).and_then(|s| r * s).ok_or(Error::Math)?;
+                self.store_pair(x, y)
+            },
+            Expr::Convert(i, a) => {
+                let a = self.eval(a)?;
+                CONVERSIONS[*i].apply(&a).ok_or(Error::Math)?
+            },
+        })
+    }
+
+    /// Stores the results of `Pol(` and `Rec(` in X and Y, giving the first.
+    fn store_pair(&mut self, first: Float, second: Float) -> Float {
+        self.set_variable(Var::X, first.clone());
+        self.set_variable(Var::Y, second);
+        first
+    }
+
+    /// Runs `f`, which may overwrite `v`, keeping the value `v` had before.
+    fn with_variable<T>(&mut self, v: Var, f: impl FnOnce(&mut Self) -> T) -> T {
+        let old = self.variable(v).clone();
+        let r = f(self);
+        self.set_variable(v, old);
+        r
+    }
+
+    /// Sums or multiplies `f` over `v` from `a` to `b`, both integers.
+    fn series(&mut self, op: SeriesOp, f: &Expr, v: Var, a: &Float, b: &Float) -> Result<Float, Error> {
+        let (Some(a), Some(b)) = (a.to_i64(), b.to_i64()) else {
+            return Err(Error::Math);
+        };
+
+        if a > b || b - a >= MAX_TERMS {
+            return Err(Error::Math);
+        }
+
+        let mut acc = match op {
+            SeriesOp::Sum => Float::ZERO,
+            SeriesOp::Product => Float::ONE,
+        };
+        for i in a..=b {
+            self.set_variable(v, Float::from_int(i));
+            let term = self.eval(f)?;
+            acc = match op {
+                SeriesOp::Sum => acc + term,
+                SeriesOp::Product => acc * term,
+            }.ok_or(Error::Math)?;
+        }
+
+        Ok(acc)
+    }
+
+    fn estimate(&self, op: UnaryOp, v: &Float) -> Result<Float, Error> {
+        let model = self.regression_model()?;
+        let c = self.regression()?;
+
+        match op {
+            UnaryOp::YHat => c.estimate_y(model, v),
+            UnaryOp::XHat2 => c.estimate_x(model, v).and_then(|(_, x2)| x2),
+            _ => c.estimate_x(model, v).map(|(x1, _)| x1),
+        }.ok_or(Error::Math)
+    }
+
+    const fn regression_model(&self) -> Result<Regression, Error> {
+        match self.mode {
+            Mode::PairedStat(r) => Ok(r),
+            _ => Err(Error::Syntax),
+        }
+    }
+
+    fn regression(&self) -> Result<fx_maths::stat::Coefficients, Error> {
+        self.regression_model()?.fit(&self.stat_data).ok_or(Error::Math)
+    }
+}
@@ -0,0 +1,250 @@
+use fx_maths::{Float, formula::FORMULAS};
+
+use super::{Calculator, Error, Menu, Token, parser};
+use super::variable::{Var, variables_in};
+
+/// Number of user formulas that can be stored.
+pub const USER_FORMULAS: usize = 4;
+/// Longest name of a user formula, in tokens.
+const NAME_LENGTH: usize = 6;
+
+/// A formula stored by the user, solved for its name in terms of the
+/// variables it uses.
+#[derive(Debug, Clone)]
+pub struct UserFormula {
+    pub name: Vec<Token>,
+    pub body: Vec<Token>,
+}
+
+impl UserFormula {
+    pub fn display_name(&self) -> String {
+        self.name.iter()
+            .flat_map(Token::as_display_block)
+            .map(|b| b.text)
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+enum Source {
+    Builtin(usize),
+    User(usize, Vec<Var>),
+}
+
+/// A formula being solved, prompting for one variable after another.
+#[derive(Debug)]
+pub struct FormulaRun {
+    source: Source,
+    values: Vec<Float>,
+    /// Variable being prompted for, `None` while the result is shown.
+    at: Option<usize>,
+}
+
+impl FormulaRun {
+    pub const fn showing_result(&self) -> bool {
+        self.at.is_none()
+    }
+}
+
+/// A user formula being written, first its name and then its body.
+#[derive(Debug)]
+pub struct FormulaEdit {
+    /// Formula being edited, `None` for a new one.
+    slot: Option<usize>,
+    name: Option<Vec<Token>>,
+}
+
+impl FormulaEdit {
+    pub const fn naming(&self) -> bool {
+        self.name.is_none()
+    }
+}
+
+impl Calculator {
+    /// The FMLA menu, built-in formulas followed by the user's.
+    pub(super) fn formula_menu(&self, page: usize) -> Option<(Menu, usize)> {
+        let names = self.user_formulas.iter().map(UserFormula::display_name).collect();
+        Some((Menu::Formula(names), page))
+    }
+
+    /// Number key or EXE in the FMLA menu. Built-in formulas can only be
+    /// solved, user formulas can also be edited (2) and deleted (3), the last
+    /// page creates a new one.
+    pub(super) fn formula_action(&mut self, page: usize, key: u8) -> Option<(Menu, usize)> {
+        let user = page.checked_sub(FORMULAS.len());
+        match (user, key) {
+            (None, 1) => self.select_formula(Source::Builtin(page)),
+            (Some(i), 1) if i < self.user_formulas.len() => {
+                let vars = variables_in(&self.user_formulas[i].body);
+                self.select_formula(Source::User(i, vars));
+            },
+            (Some(i), 2) if i < self.user_formulas.len() => {
+                self.clear();
+                self.user_input.clone_from(&self.user_formulas[i].name);
+                self.cursor_at = self.user_input.len();
+                self.formula_edit = Some(FormulaEdit { slot: Some(i), name: None });
+            },
+            (Some(i), 3) if i < self.user_formulas.len() => {
+                self.user_formulas.remove(i);
+                return self.formula_menu(page.min(FORMULAS.len() + self.user_formulas.len()));
+            },
+            (Some(_), 1) => {
+                self.clear();
+                self.formula_edit = Some(FormulaEdit { slot: None, name: None });
+            },
+            _ => return self.formula_menu(page),
+        }
+
+        None
+    }
+
+    fn select_formula(&mut self, source: Source) {
+        let values = match &source {
+            Source::Builtin(i) => vec![Float::ZERO; FORMULAS[*i].variables.len()],
+            Source::User(_, vars) => vars.iter().map(|v| self.variable(*v).clone()).collect(),
+        };
+
+        self.clear();
+        self.formula = Some(FormulaRun { source, values, at: Some(0) });
+        self.prompt_formula();
+    }
+
+    /// EXE in FMLA: takes the value of the prompted variable, keeping the
+    /// previous one when nothing was typed, and solves once all are known.
+    pub(super) fn formula_exe(&mut self) {
+        let Some(run) = &self.formula else {
+            return;
+        };
+
+        let Some(at) = run.at else {
+            // EXE on the result goes through the prompts again
+            self.formula.as_mut().unwrap().at = Some(0);
+            return self.prompt_formula();
+        };
+
+        if !self.user_input.is_empty() {
+            match parser::parse(&self.user_input).and_then(|e| self.eval(&e)) {
+                Ok(v) => self.formula.as_mut().unwrap().values[at] = v,
+                Err(e) => return self.formula_error(e),
+            }
+        }
+
+        let run = self.formula.as_mut().unwrap();
+        run.at = Some(at + 1);
+        self.prompt_formula();
+    }
+
+    /// AC in FMLA.
+    pub(super) fn exit_formula(&mut self) {
+        self.formula = None;
+        self.clear();
+    }
+
+    fn formula_error(&mut self, e: Error) {
+        self.exit_formula();
+        self.error = Some(e);
+    }
+
+    /// Prompts for the current variable, or solves once all are known.
+    fn prompt_formula(&mut self) {
+        let run = self.formula.as_ref().unwrap();
+        let at = run.at.unwrap();
+        let name = match &run.source {
+            Source::Builtin(i) => FORMULAS[*i].variables.get(at).map(|v| v.to_string()),
+            Source::User(_, vars) => vars.get(at).map(|v| v.name().to_string()),
+        };
+
+        self.clear();
+        match name {
+            Some(name) => {
+                let value = self.formula.as_ref().unwrap().values[at].clone();
+                self.label = Some(format!("{name}?"));
+                self.result = Some(value);
+            },
+            None => self.solve_formula(),
+        }
+    }
+
+    fn solve_formula(&mut self) {
+        let run = self.formula.as_mut().unwrap();
+        run.at = None;
+
+        let (name, value) = match &run.source {
+            Source::Builtin(i) => {
+                let f = &FORMULAS[*i];
+                (f.result.to_string(), (f.eval)(&run.values).ok_or(Error::Math))
+            },
+            Source::User(i, vars) => {
+                let (i, vars, values) = (*i, vars.clone(), run.values.clone());
+                for (v, value) in vars.into_iter().zip(values) {
+                    self.set_variable(v, value);
+                }
+
+                let f = &self.user_formulas[i];
+                let name = f.display_name();
+                let value = parser::parse(&f.body).and_then(|e| self.eval(&e));
+                (name, value)
+            },
+        };
+
+        match value {
+            Ok(v) => {
+                self.label = Some(format!("{name}="));
+                self.result = Some(v.clone());
+                self.answer = v;
+                self.fresh = true;
+            },
+            Err(e) => self.formula_error(e),
+        }
+    }
+
+    /// EXE while writing a user formula: moves from the name to the body,
+    /// then stores the formula once it parses.
+    pub(super) fn formula_edit_exe(&mut self) {
+        let edit = self.formula_edit.as_mut().unwrap();
+
+        let Some(name) = &edit.name else {
+            if self.user_input.is_empty() || self.user_input.len() > NAME_LENGTH {
+                self.error = Some(Error::Syntax);
+                return;
+            }
+
+            edit.name = Some(std::mem::take(&mut self.user_input));
+            let body = edit.slot.map(|i| self.user_formulas[i].body.clone()).unwrap_or_default();
+            self.clear();
+            self.cursor_at = body.len();
+            self.user_input = body;
+            return;
+        };
+
+        if let Err(e) = parser::parse(&self.user_input) {
+            self.error = Some(e);
+            return;
+        }
+
+        let formula = UserFormula {
+            name: name.clone(),
+            body: std::mem::take(&mut self.user_input),
+        };
+        let slot = match edit.slot {
+            Some(i) => {
+                self.user_formulas[i] = formula;
+                i
+            },
+            None => {
+                self.user_formulas.push(formula);
+                self.user_formulas.len() - 1
+            },
+        };
+
+        self.formula_edit = None;
+        self.clear();
+        self.menu = self.formula_menu(FORMULAS.len() + slot);
+    }
+
+    /// AC while writing a user formula.
+    pub(super) fn cancel_formula_edit(&mut self) {
+        self.formula_edit = None;
+        self.clear();
+    }
+}
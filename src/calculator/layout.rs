@@ -0,0 +1,69 @@
+//! Where the tokens of the input go on the top line. Each token is one unit
+//! for the cursor, however many columns it takes.
+
+use super::Token;
+
+#[cfg(test)]
+mod tests;
+
+/// Columns of the top line for the input, between the scroll indicators,
+/// unless the display is narrower.
+pub const INPUT_COLUMNS: usize = 16;
+
+pub struct InputLayout {
+    /// Column where each token starts, then the column after the last one.
+    starts: Vec<usize>,
+}
+
+impl InputLayout {
+    pub fn new(tokens: &[Token]) -> Self {
+        Self::from_widths(tokens.iter().map(Token::width))
+    }
+
+    fn from_widths(widths: impl IntoIterator<Item = usize>) -> Self {
+        let mut starts = vec![0];
+        for w in widths {
+            starts.push(starts[starts.len() - 1] + w);
+        }
+        Self { starts }
+    }
+
+    /// Tokens laid out.
+    pub const fn len(&self) -> usize {
+        self.starts.len() - 1
+    }
+
+    /// Column of the token at `index`, the end of the input past the last
+    /// token.
+    pub fn column(&self, index: usize) -> usize {
+        self.starts[index.min(self.len())]
+    }
+
+    /// Columns taken by the tokens from `start` up to `end`.
+    pub fn span(&self, start: usize, end: usize) -> usize {
+        self.column(end) - self.column(start)
+    }
+
+    /// Tokens shown in `columns` of the top line, the first and one past
+    /// the last, scrolled from `scroll` as little as possible to keep the
+    /// cursor in view. The cursor needs a column of its own after the last
+    /// token.
+    pub fn window(&self, scroll: usize, cursor: usize, columns: usize) -> (usize, usize) {
+        let cursor = cursor.min(self.len());
+        let mut start = scroll.min(cursor);
+        while self.span(start, cursor) >= columns {
+            start += 1;
+        }
+        // no empty columns after the end of the input while it is scrolled
+        while start > 0 && self.span(start - 1, self.len()) < columns {
+            start -= 1;
+        }
+
+        let mut end = start;
+        while end < self.len() && self.span(start, end + 1) <= columns {
+            end += 1;
+        }
+
+        (start, end)
+    }
+}
@@ -0,0 +1,60 @@
+use super::*;
+
+#[test]
+fn columns() {
+    use Token::*;
+
+    // e^( 2 + x̂ ⒑^(
+    let layout = InputLayout::new(&[EPower, _2, Add, XHat, TenPower]);
+    assert_eq!(layout.len(), 5);
+    assert_eq!(
+        (0..=5).map(|i| layout.column(i)).collect::<Vec<_>>(),
+        vec![0, 3, 4, 5, 6, 9],
+    );
+    assert_eq!(layout.column(9), 9);
+    assert_eq!(layout.span(1, 4), 3);
+
+    let empty = InputLayout::new(&[]);
+    assert_eq!(empty.column(0), 0);
+    assert_eq!(empty.window(0, 0, INPUT_COLUMNS), (0, 0));
+}
+
+#[test]
+fn window() {
+    let short = InputLayout::from_widths([1; 10]);
+    assert_eq!(short.window(0, 10, INPUT_COLUMNS), (0, 10));
+    assert_eq!(short.window(0, 0, INPUT_COLUMNS), (0, 10));
+
+    // the cursor at the end takes the sixteenth column
+    let full = InputLayout::from_widths([1; 16]);
+    assert_eq!(full.window(0, 16, INPUT_COLUMNS), (1, 16));
+    assert_eq!(full.window(0, 15, INPUT_COLUMNS), (0, 16));
+
+    let long = InputLayout::from_widths([1; 20]);
+    assert_eq!(long.window(0, 20, INPUT_COLUMNS), (5, 20));
+    // moving back keeps the scroll until the cursor reaches the left edge
+    assert_eq!(long.window(5, 10, INPUT_COLUMNS), (5, 20));
+    assert_eq!(long.window(5, 3, INPUT_COLUMNS), (3, 19));
+    assert_eq!(long.window(5, 0, INPUT_COLUMNS), (0, 16));
+    // a scroll past the input is pulled back
+    assert_eq!(long.window(30, 40, INPUT_COLUMNS), (5, 20));
+    assert_eq!(long.window(18, 20, INPUT_COLUMNS), (5, 20));
+}
+
+#[test]
+fn wide_tokens() {
+    // a wide token is left out whole rather than cut
+    let layout = InputLayout::from_widths([5, 5, 5, 5]);
+    assert_eq!(layout.window(0, 0, INPUT_COLUMNS), (0, 3));
+    assert_eq!(layout.window(0, 3, INPUT_COLUMNS), (0, 3));
+    assert_eq!(layout.window(0, 4, INPUT_COLUMNS), (1, 4));
+    assert_eq!(layout.span(1, 3), 10);
+}
+
+#[test]
+fn narrow() {
+    let long = InputLayout::from_widths([1; 20]);
+    assert_eq!(long.window(0, 20, 10), (11, 20));
+    assert_eq!(long.window(11, 5, 10), (5, 15));
+    assert_eq!(long.window(0, 0, 10), (0, 10));
+}
@@ -0,0 +1,521 @@
+use fx_maths::{Float, constants::CONSTANTS};
+
+use super::{Error, Token};
+use super::variable::Var;
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Number(Float),
+    Ans,
+    Variable(Var),
+    Stat(StatVar),
+    Unary(UnaryOp, Box<Self>),
+    Binary(BinaryOp, Box<Self>, Box<Self>),
+    /// `∫(f(X), a, b, tol)`, the tolerance is optional.
+    Integral(Box<Self>, Box<Self>, Box<Self>, Option<Box<Self>>),
+    /// `d/dx(f(X), a, tol)`, the tolerance is optional.
+    Derivative(Box<Self>, Box<Self>, Option<Box<Self>>),
+    /// `Σ(f(V), V, a, b)` or `Π(f(V), V, a, b)`
+    Series(SeriesOp, Box<Self>, Var, Box<Self>, Box<Self>),
+    /// `Ran#`
+    Random,
+    /// `RanInt#(a, b)`
+    RandomInt(Box<Self>, Box<Self>),
+    /// `Pol(x, y)`, gives `r` and stores `r` and `θ` in X and Y.
+    Polar(Box<Self>, Box<Self>),
+    /// `Rec(r, θ)`, gives `x` and stores `x` and `y` in X and Y.
+    Rectangular(Box<Self>, Box<Self>),
+    /// Unit conversion, an index into the catalogue.
+    Convert(usize, Box<Self>),
+}
+
+impl Expr {
+    /// Names of the two results of a coordinate conversion, shown with the
+    /// values left in X and Y.
+    pub const fn coordinates(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Self::Polar(..) => Some(("r", "θ")),
+            Self::Rectangular(..) => Some(("x", "y")),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SeriesOp {
+    Sum, Product,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum StatVar {
+    A, B, C, R,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum UnaryOp {
+    Negate, SquareRoot, EPower, TenPower,
+    XHat, XHat1, XHat2, YHat, Factorial,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum BinaryOp {
+    Add, Subtract, Multiply, Divide, Power,
+    /// `a⅃b`
+    Fraction,
+    Permutations, Combinations,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Relation {
+    Equal, NotEqual, Greater, GreaterEqual, Less, LessEqual,
+}
+
+/// A single statement of a program, between `:` or `◢` separators.
+#[derive(Debug, Clone)]
+pub enum Statement {
+    /// `expr` or `expr→V`
+    Eval(Expr, Option<Var>),
+    /// `?→V`
+    Prompt(Var),
+    Label(u8),
+    Goto(u8),
+    Isz(Var),
+    Dsz(Var),
+    /// `a=b⇒`, the next statement is skipped when it does not hold.
+    Condition(Expr, Relation, Expr),
+}
+
+/// Parses a program into statements, each flagged with whether it ends in
+/// `◢`.
+pub fn parse_program(tokens: &[Token]) -> Result<Vec<(Statement, bool)>, Error> {
+    let mut statements = Vec::new();
+
+    for segment in tokens.split_inclusive(|t| matches!(t, Token::Colon | Token::Display | Token::Then)) {
+        let (body, end) = match segment.split_last() {
+            Some((end @ (Token::Colon | Token::Display | Token::Then), body)) => (body, Some(end)),
+            _ => (segment, None),
+        };
+
+        if body.is_empty() {
+            if matches!(end, Some(Token::Then)) {
+                return Err(Error::Syntax);
+            }
+
+            continue;
+        }
+
+        let mut p = Parser { tokens: body, at: 0 };
+        let statement = if matches!(end, Some(Token::Then)) {
+            p.condition()?
+        } else {
+            p.statement()?
+        };
+        p.end()?;
+
+        statements.push((statement, matches!(end, Some(Token::Display))));
+    }
+
+    Ok(statements)
+}
+
+/// Parses a list of comma separated expressions.
+pub fn parse_list(tokens: &[Token]) -> Result<Vec<Expr>, Error> {
+    let mut p = Parser { tokens, at: 0 };
+    let mut list = vec![p.expr()?];
+
+    while p.eat(|t| matches!(t, Token::Comma)) {
+        list.push(p.expr()?);
+    }
+
+    p.end()?;
+    Ok(list)
+}
+
+/// Parses an equation `lhs=rhs`, a lone expression is taken as `expr=0`.
+pub fn parse_equation(tokens: &[Token]) -> Result<(Expr, Expr), Error> {
+    let mut p = Parser { tokens, at: 0 };
+    let lhs = p.expr()?;
+    let rhs = if p.eat(|t| matches!(t, Token::Equal)) {
+        p.expr()?
+    } else {
+        Expr::Number(Float::ZERO)
+    };
+
+    p.end()?;
+    Ok((lhs, rhs))
+}
+
+pub fn parse(tokens: &[Token]) -> Result<Expr, Error> {
+    let mut p = Parser { tokens, at: 0 };
+    let e = p.expr()?;
+    p.end()?;
+    Ok(e)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    at: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.at)
+    }
+
+    fn eat(&mut self, f: impl Fn(&Token) -> bool) -> bool {
+        if self.peek().is_some_and(f) {
+            self.at += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn end(&self) -> Result<(), Error> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(Error::Syntax),
+        }
+    }
+
+    fn statement(&mut self) -> Result<Statement, Error> {
+        let Some(t) = self.peek() else {
+            return Err(Error::Syntax);
+        };
+
+        if !matches!(t, Token::Prompt | Token::Label | Token::Goto | Token::Isz | Token::Dsz) {
+            let e = self.expr()?;
+            let store = if self.eat(|t| matches!(t, Token::Store)) {
+                Some(self.variable()?)
+            } else {
+                None
+            };
+
+            return Ok(Statement::Eval(e, store));
+        }
+
+        self.at += 1;
+        Ok(match t {
+            Token::Prompt if self.eat(|t| matches!(t, Token::Store)) => Statement::Prompt(self.variable()?),
+            Token::Label => Statement::Label(self.label()?),
+            Token::Goto => Statement::Goto(self.label()?),
+            Token::Isz => Statement::Isz(self.variable()?),
+            Token::Dsz => Statement::Dsz(self.variable()?),
+            _ => return Err(Error::Syntax),
+        })
+    }
+
+    /// Label number of `Lbl` and `Goto`, a single digit.
+    fn label(&mut self) -> Result<u8, Error> {
+        let n = self.peek().and_then(Token::digit).ok_or(Error::Syntax)?;
+        self.at += 1;
+        Ok(n as u8 - b'0')
+    }
+
+    fn condition(&mut self) -> Result<Statement, Error> {
+        let lhs = self.expr()?;
+        let relation = match self.peek() {
+            Some(Token::Equal) => Relation::Equal,
+            Some(Token::NotEqual) => Relation::NotEqual,
+            Some(Token::Greater) => Relation::Greater,
+            Some(Token::GreaterEqual) => Relation::GreaterEqual,
+            Some(Token::Less) => Relation::Less,
+            Some(Token::LessEqual) => Relation::LessEqual,
+            _ => return Err(Error::Syntax),
+        };
+
+        self.at += 1;
+        Ok(Statement::Condition(lhs, relation, self.expr()?))
+    }
+
+    fn variable(&mut self) -> Result<Var, Error> {
+        match self.peek() {
+            Some(&Token::Variable(v)) => {
+                self.at += 1;
+                Ok(v)
+            },
+            _ => Err(Error::Syntax),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.term()?;
+
+        loop {
+            let op = match self.peek() {
+                Some(Token::Add) => BinaryOp::Add,
+                Some(Token::Subtract) => BinaryOp::Subtract,
+                _ => return Ok(lhs),
+            };
+
+            self.at += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.implied()?;
+
+        loop {
+            let op = match self.peek() {
+                Some(Token::Multiply) => BinaryOp::Multiply,
+                Some(Token::Divide) => BinaryOp::Divide,
+                _ => return Ok(lhs),
+            };
+
+            self.at += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.implied()?));
+        }
+    }
+
+    /// Abbreviated multiplication, e.g. `2√(3)` or `3(4)`.
+    fn implied(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.combinatoric()?;
+
+        while self.peek().is_some_and(Token::starts_operand) {
+            lhs = Expr::Binary(BinaryOp::Multiply, Box::new(lhs), Box::new(self.combinatoric()?));
+        }
+
+        Ok(lhs)
+    }
+
+    /// `nPr` and `nCr`, binding tighter than multiplication.
+    fn combinatoric(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.prefix()?;
+
+        loop {
+            let op = match self.peek() {
+                Some(Token::Permutations) => BinaryOp::Permutations,
+                Some(Token::Combinations) => BinaryOp::Combinations,
+                _ => return Ok(lhs),
+            };
+
+            self.at += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.prefix()?));
+        }
+    }
+
+    fn prefix(&mut self) -> Result<Expr, Error> {
+        if self.eat(|t| matches!(t, Token::Negative)) {
+            return Ok(Expr::Unary(UnaryOp::Negate, Box::new(self.prefix()?)));
+        }
+
+        if self.eat(|t| matches!(t, Token::Integral)) {
+            let mut args = self.arguments(3, 4)?.into_iter().map(Box::new);
+            let (f, a, b) = (args.next().unwrap(), args.next().unwrap(), args.next().unwrap());
+            return Ok(Expr::Integral(f, a, b, args.next()));
+        }
+
+        if self.eat(|t| matches!(t, Token::Derivative)) {
+            let mut args = self.arguments(2, 3)?.into_iter().map(Box::new);
+            let (f, a) = (args.next().unwrap(), args.next().unwrap());
+            return Ok(Expr::Derivative(f, a, args.next()));
+        }
+
+        let series = match self.peek() {
+            Some(Token::Sum) => Some(SeriesOp::Sum),
+            Some(Token::Product) => Some(SeriesOp::Product),
+            _ => None,
+        };
+        if let Some(op) = series {
+            self.at += 1;
+            let mut args = self.arguments(4, 4)?.into_iter().map(Box::new);
+            let (f, v, a, b) = (args.next().unwrap(), args.next().unwrap(), args.next().unwrap(), args.next().unwrap());
+            let Expr::Variable(v) = *v else {
+                return Err(Error::Syntax);
+            };
+
+            return Ok(Expr::Series(op, f, v, a, b));
+        }
+
+        let pair = match self.peek() {
+            Some(Token::RandomInt) => Some(Expr::RandomInt as fn(_, _) -> _),
+            Some(Token::Pol) => Some(Expr::Polar as fn(_, _) -> _),
+            Some(Token::Rec) => Some(Expr::Rectangular as fn(_, _) -> _),
+            _ => None,
+        };
+        if let Some(f) = pair {
+            self.at += 1;
+            let mut args = self.arguments(2, 2)?.into_iter().map(Box::new);
+            return Ok(f(args.next().unwrap(), args.next().unwrap()));
+        }
+
+        let f = match self.peek() {
+            Some(Token::SquareRoot) => UnaryOp::SquareRoot,
+            Some(Token::EPower) => UnaryOp::EPower,
+            Some(Token::TenPower) => UnaryOp::TenPower,
+            _ => return self.fraction(),
+        };
+
+        self.at += 1;
+        Ok(Expr::Unary(f, Box::new(self.bracketed()?)))
+    }
+
+    fn fraction(&mut self) -> Result<Expr, Error> {
+        let a = self.postfix()?;
+        if !self.eat(|t| matches!(t, Token::Fraction)) {
+            return Ok(a);
+        }
+
+        let b = self.postfix()?;
+        if !self.eat(|t| matches!(t, Token::Fraction)) {
+            return Ok(Expr::Binary(BinaryOp::Fraction, Box::new(a), Box::new(b)));
+        }
+
+        // a⅃b⅃c is the mixed fraction a + b/c
+        let c = self.postfix()?;
+        Ok(Expr::Binary(
+            BinaryOp::Add,
+            Box::new(a),
+            Box::new(Expr::Binary(BinaryOp::Fraction, Box::new(b), Box::new(c))),
+        ))
+    }
+
+    fn postfix(&mut self) -> Result<Expr, Error> {
+        let mut e = self.primary()?;
+
+        loop {
+            let op = match self.peek() {
+                Some(Token::Power) => {
+                    self.at += 1;
+                    e = Expr::Binary(BinaryOp::Power, Box::new(e), Box::new(self.bracketed()?));
+                    continue;
+                },
+                Some(Token::XHat) => UnaryOp::XHat,
+                Some(Token::XHat1) => UnaryOp::XHat1,
+                Some(Token::XHat2) => UnaryOp::XHat2,
+                Some(Token::YHat) => UnaryOp::YHat,
+                Some(Token::Factorial) => UnaryOp::Factorial,
+                Some(&Token::Convert(i)) => {
+                    self.at += 1;
+                    e = Expr::Convert(i, Box::new(e));
+                    continue;
+                },
+                _ => return Ok(e),
+            };
+
+            self.at += 1;
+            e = Expr::Unary(op, Box::new(e));
+        }
+    }
+
+    /// Contents of an opened bracket, the closing one can be left out at the
+    /// end of the input.
+    fn bracketed(&mut self) -> Result<Expr, Error> {
+        let e = self.expr()?;
+        if self.peek().is_some() && !self.eat(|t| matches!(t, Token::BracketEnd)) {
+            return Err(Error::Syntax);
+        }
+
+        Ok(e)
+    }
+
+    /// Comma separated arguments of a function, up to its closing bracket.
+    fn arguments(&mut self, min: usize, max: usize) -> Result<Vec<Expr>, Error> {
+        let mut args = vec![self.expr()?];
+        while args.len() < max && self.eat(|t| matches!(t, Token::Comma)) {
+            args.push(self.expr()?);
+        }
+
+        if args.len() < min || self.peek().is_some() && !self.eat(|t| matches!(t, Token::BracketEnd)) {
+            return Err(Error::Syntax);
+        }
+
+        Ok(args)
+    }
+
+    fn primary(&mut self) -> Result<Expr, Error> {
+        let Some(t) = self.peek() else {
+            return Err(Error::Syntax);
+        };
+
+        if t.digit().is_some() || matches!(t, Token::Dot | Token::Exp) {
+            return self.number();
+        }
+
+        self.at += 1;
+        Ok(match t {
+            Token::BracketStart => self.bracketed()?,
+            Token::E => Expr::Number(Float::E),
+            Token::Ans => Expr::Ans,
+            Token::Random => Expr::Random,
+            &Token::Constant(i) => Expr::Number(CONSTANTS[i].value.clone()),
+            &Token::Variable(v) => Expr::Variable(v),
+            Token::StatA => Expr::Stat(StatVar::A),
+            Token::StatB => Expr::Stat(StatVar::B),
+            Token::StatC => Expr::Stat(StatVar::C),
+            Token::StatR => Expr::Stat(StatVar::R),
+            _ => return Err(Error::Syntax),
+        })
+    }
+
+    fn number(&mut self) -> Result<Expr, Error> {
+        let mut s = String::new();
+        let mut dot = false;
+
+        while let Some(t) = self.peek() {
+            match (t, t.digit()) {
+                (_, Some(d)) => s.push(d),
+                (Token::Dot, _) if !dot => {
+                    s.push('.');
+                    dot = true;
+                },
+                (Token::Dot, _) => return Err(Error::Syntax),
+                _ => break,
+            }
+
+            self.at += 1;
+        }
+
+        // a lone `ᴇ3` means `1ᴇ3`
+        if s.is_empty() {
+            s.push('1');
+        } else if s == "." {
+            return Err(Error::Syntax);
+        }
+
+        if self.eat(|t| matches!(t, Token::Exp)) {
+            s.push('e');
+            if self.eat(|t| matches!(t, Token::Negative | Token::Subtract)) {
+                s.push('-');
+            } else {
+                self.eat(|t| matches!(t, Token::Add));
+            }
+
+            let start = s.len();
+            while let Some(d) = self.peek().and_then(Token::digit) {
+                s.push(d);
+                self.at += 1;
+            }
+
+            if s.len() == start {
+                return Err(Error::Syntax);
+            }
+        }
+
+        s.parse().map(Expr::Number).map_err(|_| Error::Math)
+    }
+}
+
+impl Token {
+    const fn digit(&self) -> Option<char> {
+        use Token::*;
+        Some(match self {
+            _0 => '0', _1 => '1', _2 => '2', _3 => '3', _4 => '4',
+            _5 => '5', _6 => '6', _7 => '7', _8 => '8', _9 => '9',
+            _ => return None,
+        })
+    }
+
+    /// Whether an operand can start with this token, used for abbreviated
+    /// multiplication.
+    const fn starts_operand(&self) -> bool {
+        use Token::*;
+        matches!(
+            self,
+            BracketStart | E | Ans | SquareRoot | EPower | TenPower | Integral | Derivative | Sum | Product
+                | Random | RandomInt | Pol | Rec | Constant(_)
+                | Variable(_) | StatA | StatB | StatC | StatR
+        )
+    }
+}
@@ -0,0 +1,251 @@
+use fx_maths::Float;
+
+use super::{Calculator, Error, Menu, Mode, Token, parser};
+use super::parser::{Relation, Statement};
+use super::variable::Var;
+
+/// Number of program areas, P1 to P4.
+pub const PROGRAM_AREAS: usize = 4;
+/// Steps shared between all program areas, each token takes one step.
+pub const PROGRAM_STEPS: usize = 680;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    /// Mode the program runs in.
+    pub mode: Mode,
+    pub tokens: Vec<Token>,
+}
+
+impl Program {
+    pub const fn new() -> Self {
+        Self {
+            mode: Mode::Computation,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+impl Calculator {
+    pub(super) fn remaining_steps(&self) -> usize {
+        PROGRAM_STEPS - self.programs.iter().map(|p| p.tokens.len()).sum::<usize>()
+    }
+
+    /// Sets the run mode of a program area and opens it in the editor.
+    pub(super) fn edit_program(&mut self, slot: usize, mode: Mode) {
+        self.programs[slot].mode = mode;
+        self.user_input.clone_from(&self.programs[slot].tokens);
+        self.cursor_at = self.user_input.len();
+        self.editing = Some(slot);
+    }
+
+    /// Keeps the program being edited in sync with the input and brings back
+    /// the area selection when the editor is closed.
+    pub(super) fn sync_program(&mut self) {
+        if !matches!(self.mode, Mode::Program) {
+            self.editing = None;
+            return;
+        }
+
+        match (self.editing, &self.menu) {
+            (Some(slot), None) => self.programs[slot].tokens.clone_from(&self.user_input),
+            (None, None) => self.menu = Some((Menu::ProgramSlot, 0)),
+            _ => (),
+        }
+    }
+
+    /// AC in the editor, back to the area selection.
+    pub(super) fn close_editor(&mut self) {
+        self.editing = None;
+        self.clear();
+    }
+}
+
+/// Statements executed per tick, so that a running program can still be
+/// interrupted with AC.
+const STATEMENTS_PER_TICK: usize = 100;
+
+#[derive(Debug)]
+pub struct Run {
+    statements: Vec<(Statement, bool)>,
+    /// Index of the next statement.
+    at: usize,
+    state: RunState,
+    /// Mode to go back to once the program ends.
+    previous_mode: Mode,
+    last: Option<Float>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState {
+    Running,
+    /// Stopped at `◢` until EXE.
+    Paused,
+    /// Waiting on `?→V` for input.
+    Prompt(Var),
+}
+
+impl Calculator {
+    pub(super) fn run_program(&mut self, slot: usize) {
+        let statements = match parser::parse_program(&self.programs[slot].tokens) {
+            Ok(s) => s,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            },
+        };
+
+        self.clear();
+        self.running = Some(Run {
+            statements,
+            at: 0,
+            state: RunState::Running,
+            previous_mode: self.mode,
+            last: None,
+        });
+        self.mode = self.programs[slot].mode;
+    }
+
+    /// Whether a program is running on its own, not waiting for a key.
+    pub(super) const fn program_busy(&self) -> bool {
+        matches!(self.running, Some(Run { state: RunState::Running, .. }))
+    }
+
+    /// Whether a running program is taking input.
+    pub(super) const fn prompting(&self) -> bool {
+        matches!(self.running, Some(Run { state: RunState::Prompt(_), .. }))
+    }
+
+    /// EXE while a program is running: continues after `◢` or takes the
+    /// input for `?→V`.
+    pub(super) fn resume_program(&mut self) {
+        let Some(run) = &self.running else {
+            return;
+        };
+
+        if let RunState::Prompt(v) = run.state {
+            if !self.user_input.is_empty() {
+                match parser::parse(&self.user_input).and_then(|e| self.eval(&e)) {
+                    Ok(value) => self.set_variable(v, value),
+                    Err(e) => return self.program_error(e),
+                }
+            }
+        }
+
+        self.clear();
+        if let Some(run) = &mut self.running {
+            run.state = RunState::Running;
+        }
+    }
+
+    /// AC while a program is running.
+    pub(super) fn stop_program(&mut self) {
+        if let Some(run) = self.running.take() {
+            self.mode = run.previous_mode;
+        }
+
+        self.clear();
+    }
+
+    fn program_error(&mut self, e: Error) {
+        self.stop_program();
+        self.error = Some(e);
+    }
+
+    /// Executes a bounded number of statements of the running program.
+    pub(super) fn step_program(&mut self) {
+        for _ in 0..STATEMENTS_PER_TICK {
+            let Some(run) = &self.running else {
+                return;
+            };
+
+            if run.state != RunState::Running {
+                return;
+            }
+
+            let Some((statement, display)) = run.statements.get(run.at).cloned() else {
+                return self.finish_program();
+            };
+
+            if let Err(e) = self.execute_statement(statement, display) {
+                return self.program_error(e);
+            }
+        }
+    }
+
+    fn execute_statement(&mut self, statement: Statement, display: bool) -> Result<(), Error> {
+        let mut next = self.running.as_ref().unwrap().at + 1;
+        let mut value = None;
+
+        match statement {
+            Statement::Eval(e, store) => {
+                let v = self.eval(&e)?;
+                if let Some(var) = store {
+                    self.set_variable(var, v.clone());
+                }
+
+                self.answer = v.clone();
+                value = Some(v);
+            },
+            Statement::Prompt(var) => {
+                self.label = Some(format!("{}?", var.name()));
+                self.result = Some(self.variable(var).clone());
+                self.running.as_mut().unwrap().state = RunState::Prompt(var);
+            },
+            Statement::Label(_) => (),
+            Statement::Goto(n) => {
+                next = self.running.as_ref().unwrap().statements.iter()
+                    .position(|(s, _)| matches!(s, Statement::Label(l) if *l == n))
+                    .ok_or(Error::Go)? + 1;
+            },
+            Statement::Isz(var) | Statement::Dsz(var) => {
+                let step = if matches!(statement, Statement::Isz(_)) { Float::ONE } else { -Float::ONE };
+                let v = (self.variable(var).clone() + step).ok_or(Error::Math)?;
+                if v.is_zero() {
+                    next += 1;
+                }
+
+                self.set_variable(var, v.clone());
+                value = Some(v);
+            },
+            Statement::Condition(lhs, relation, rhs) => {
+                let lhs = self.eval(&lhs)?;
+                let rhs = self.eval(&rhs)?;
+                let ordering = lhs.partial_cmp(&rhs).ok_or(Error::Math)?;
+                let holds = match relation {
+                    Relation::Equal => ordering.is_eq(),
+                    Relation::NotEqual => ordering.is_ne(),
+                    Relation::Greater => ordering.is_gt(),
+                    Relation::GreaterEqual => ordering.is_ge(),
+                    Relation::Less => ordering.is_lt(),
+                    Relation::LessEqual => ordering.is_le(),
+                };
+
+                if !holds {
+                    next += 1;
+                }
+            },
+        }
+
+        let run = self.running.as_mut().unwrap();
+        run.at = next;
+        if value.is_some() {
+            run.last.clone_from(&value);
+        }
+
+        if display && value.is_some() {
+            run.state = RunState::Paused;
+            self.label = Some("Disp".to_string());
+            self.result = value;
+        }
+
+        Ok(())
+    }
+
+    fn finish_program(&mut self) {
+        let run = self.running.take().unwrap();
+        self.mode = run.previous_mode;
+        self.clear();
+        self.result = run.last;
+        self.fresh = true;
+    }
+}
@@ -0,0 +1,98 @@
+//! Running the calculator from a script of key names without a terminal,
+//! for checking it against the outputs of the hardware. A script is names
+//! of keys from `keymap.toml` in any case, like `SHIFT sin 3 0 EXE`, and
+//! `#` starts a comment.
+
+use std::fmt;
+
+use super::{Calculator, Key};
+
+/// Ticks a program may take after the last key, as a program looping
+/// forever would never stop.
+const SETTLE_TICKS: usize = 10_000;
+
+/// Calculator keys by the names used in scripts and keymap files.
+pub const KEY_NAMES: &[(&str, Key)] = {
+    use Key::*;
+    &[
+        ("Shift", Shift), ("Alpha", Alpha), ("Up", Up), ("Right", Right), ("Mode", Mode),
+        ("Prog", Prog), ("Fmla", Fmla), ("Left", Left), ("Down", Down), ("PowNegOne", PowNegOne), ("Cubed", Cubed),
+        ("Fraction", Fraction), ("SquareRoot", SquareRoot), ("Squared", Squared), ("Power", Power), ("Log", Log), ("Ln", Ln),
+        ("Negative", Negative), ("Base60", Base60), ("Hyp", Hyp), ("Sin", Sin), ("Cos", Cos), ("Tan", Tan),
+        ("Rcl", Rcl), ("Eng", Eng), ("BracketStart", BracketStart), ("BracketEnd", BracketEnd), ("Comma", Comma), ("MPlus", MPlus),
+        ("Del", Del), ("Ac", Ac), ("Add", Add), ("Subtract", Subtract), ("Multiply", Multiply), ("Divide", Divide),
+        ("0", _0), ("1", _1), ("2", _2), ("3", _3), ("4", _4), ("5", _5), ("6", _6), ("7", _7), ("8", _8), ("9", _9),
+        ("Dot", Dot), ("Exp", Exp), ("Ans", Ans), ("Exe", Exe),
+    ]
+};
+
+impl Key {
+    pub fn name(self) -> &'static str {
+        KEY_NAMES.iter().find(|(_, k)| *k == self).map_or("?", |(n, _)| n)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        KEY_NAMES.iter().find(|(n, _)| *n == name).map(|(_, k)| *k)
+    }
+}
+
+/// A word of a script that is not a key name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    pub line: usize,
+    pub word: String,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: unknown calculator key `{}`", self.line, self.word)
+    }
+}
+
+impl std::error::Error for UnknownKey {}
+
+/// The keys of a script in the order they are pressed.
+pub fn parse_script(text: &str) -> Result<Vec<Key>, UnknownKey> {
+    let mut keys = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split_once('#').map_or(line, |(keys, _)| keys);
+        for word in line.split_whitespace() {
+            let key = KEY_NAMES.iter().find(|(name, _)| name.eq_ignore_ascii_case(word));
+            match key {
+                Some((_, k)) => keys.push(*k),
+                None => return Err(UnknownKey { line: n + 1, word: word.to_string() }),
+            }
+        }
+    }
+
+    Ok(keys)
+}
+
+impl Calculator {
+    /// Presses keys in turn, letting programs run between them as they
+    /// would while waiting for the next key.
+    pub fn run_keys(&mut self, keys: &[Key]) {
+        for k in keys {
+            self.press(*k);
+            self.tick();
+            self.settle();
+        }
+    }
+
+    fn settle(&mut self) {
+        for _ in 0..SETTLE_TICKS {
+            if !self.program_busy() {
+                return;
+            }
+            self.tick();
+        }
+    }
+
+    /// The display as text, the status line, the top line and the bottom
+    /// line.
+    pub fn display_text(&self) -> String {
+        let display = self.get_display();
+        let top: String = display.top.iter().map(|b| b.text.as_str()).collect();
+        format!("{}\n{}\n{}", display.stat.trim_end(), top.trim_end(), display.bot.trim_end())
+    }
+}
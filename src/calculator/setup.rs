@@ -0,0 +1,139 @@
+use fx_maths::{Float, trig::AngleUnit};
+
+use super::{Calculator, Key, Menu};
+
+/// Everything chosen in the SETUP menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub angle: AngleUnit,
+    pub display: DisplayFormat,
+    pub fraction: FractionFormat,
+    pub complex: ComplexFormat,
+    /// Whether statistical data is entered with a frequency, `x,y,freq`.
+    pub stat_frequency: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayFormat {
+    /// Fixed number of decimal places, 0 to 9.
+    Fix(u8),
+    /// Fixed number of significant digits, 1 to 10 with 0 meaning 10.
+    Sci(u8),
+    /// Scientific notation only for small and large results, 1 or 2.
+    Norm(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FractionFormat {
+    /// `a⅃b⅃c`
+    Mixed,
+    /// `d⅃c`
+    Improper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexFormat {
+    /// `a+bi`
+    Rectangular,
+    /// `r∠θ`
+    Polar,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            angle: AngleUnit::Degree,
+            display: DisplayFormat::Norm(1),
+            fraction: FractionFormat::Mixed,
+            complex: ComplexFormat::Rectangular,
+            stat_frequency: false,
+        }
+    }
+}
+
+/// Significant digits shown on the display.
+const DIGITS: u32 = 10;
+
+impl Settings {
+    /// Formats a result in the display format.
+    pub fn format(&self, x: &Float) -> String {
+        match self.display {
+            DisplayFormat::Fix(places) => {
+                let sci = x.sci_exponent();
+                if !x.is_zero() && sci >= DIGITS as i32 {
+                    return x.format_sci(DIGITS);
+                }
+
+                // never more digits than fit on the display
+                let places = (places as i32).min(DIGITS as i32 - 1 - sci.max(0));
+                x.format_fixed(Some(places as u32))
+            },
+            DisplayFormat::Sci(0) => x.format_sci_fixed(DIGITS),
+            DisplayFormat::Sci(digits) => x.format_sci_fixed(digits as u32),
+            DisplayFormat::Norm(1) => x.format_norm(DIGITS, -2),
+            DisplayFormat::Norm(_) => x.format_norm(DIGITS, -9),
+        }
+    }
+
+    /// Indicators of the settings on the status line.
+    pub fn status_name(&self) -> String {
+        let display = match self.display {
+            DisplayFormat::Fix(_) => "FIX",
+            DisplayFormat::Sci(_) => "SCI",
+            DisplayFormat::Norm(_) => "   ",
+        };
+        let angle = match self.angle {
+            AngleUnit::Degree => "D",
+            AngleUnit::Radian => "R",
+            AngleUnit::Gradian => "G",
+        };
+        let complex = match self.complex {
+            ComplexFormat::Rectangular => "   ",
+            ComplexFormat::Polar => "r∠θ",
+        };
+
+        format!("{display} {angle} {complex}")
+    }
+}
+
+impl DisplayFormat {
+    /// The same format with the number of digits typed after choosing it,
+    /// `None` if out of range.
+    pub const fn with_digits(self, n: u8) -> Option<Self> {
+        match self {
+            Self::Fix(_) if n <= 9 => Some(Self::Fix(n)),
+            Self::Sci(_) if n <= 9 => Some(Self::Sci(n)),
+            Self::Norm(_) if n == 1 || n == 2 => Some(Self::Norm(n)),
+            _ => None,
+        }
+    }
+
+    /// Prompt for the number of digits.
+    pub const fn prompt(self) -> &'static str {
+        match self {
+            Self::Fix(_) => "Fix 0~9?",
+            Self::Sci(_) => "Sci 0~9?",
+            Self::Norm(_) => "Norm 1~2?",
+        }
+    }
+}
+
+impl Calculator {
+    /// SHIFT SETUP
+    pub(super) fn setup(&mut self) {
+        self.menu = Some((Menu::Setup, 0));
+        self.modifier_key = None;
+    }
+
+    /// Sets the display format to `format` with the digits of the key just
+    /// pressed, leaving the menu open if they are out of range.
+    pub(super) fn set_display(&mut self, format: DisplayFormat) -> Option<(Menu, usize)> {
+        match self.pending_key.as_ref().and_then(Key::digit).and_then(|n| format.with_digits(n)) {
+            Some(d) => {
+                self.settings.display = d;
+                None
+            },
+            None => Some((Menu::SetupDigits(format), 0)),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use fx_maths::solve::Solver;
+
+use super::{Calculator, Error, Token, parser};
+use super::parser::{BinaryOp, Expr};
+use super::variable::{Var, variables_in};
+
+/// An equation being solved with SOLVE, SHIFT FMLA.
+#[derive(Debug)]
+pub struct SolveRun {
+    equation: Vec<Token>,
+    /// `L–R` of the equation, a root of which is looked for.
+    residual: Expr,
+    vars: Vec<Var>,
+    state: SolveState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SolveState {
+    /// Prompting for the value of a variable, the one SOLVE is pressed on is
+    /// solved for starting from its value.
+    Prompt(usize),
+    Solution,
+    /// `L–R` at the solution.
+    Residual,
+}
+
+impl SolveRun {
+    pub const fn showing_result(&self) -> bool {
+        matches!(self.state, SolveState::Solution | SolveState::Residual)
+    }
+}
+
+impl Calculator {
+    /// SOLVE, SHIFT FMLA: starts solving the input, or solves for the
+    /// variable being prompted for.
+    pub(super) fn solve(&mut self) {
+        self.modifier_key = None;
+
+        let Some(run) = &self.solving else {
+            return self.start_solve();
+        };
+
+        match run.state {
+            SolveState::Prompt(at) => {
+                if let Err(e) = self.take_solve_value(at) {
+                    return self.solve_error(e);
+                }
+
+                let v = self.solving.as_ref().unwrap().vars[at];
+                self.solve_for(v);
+            },
+            _ => self.prompt_solve(0),
+        }
+    }
+
+    fn start_solve(&mut self) {
+        if self.user_input.is_empty() || self.editing.is_some() {
+            return;
+        }
+
+        let (lhs, rhs) = match parser::parse_equation(&self.user_input) {
+            Ok(e) => e,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            },
+        };
+
+        let vars = variables_in(&self.user_input);
+        if vars.is_empty() {
+            self.error = Some(Error::Syntax);
+            return;
+        }
+
+        self.solving = Some(SolveRun {
+            equation: self.user_input.clone(),
+            residual: Expr::Binary(BinaryOp::Subtract, Box::new(lhs), Box::new(rhs)),
+            vars,
+            state: SolveState::Prompt(0),
+        });
+        self.prompt_solve(0);
+    }
+
+    /// EXE while solving: takes the prompted value and moves on to the next
+    /// variable, or goes back to the prompts from the solution.
+    pub(super) fn solve_exe(&mut self) {
+        let run = self.solving.as_ref().unwrap();
+        match run.state {
+            SolveState::Prompt(at) => {
+                if let Err(e) = self.take_solve_value(at) {
+                    return self.solve_error(e);
+                }
+
+                let next = (at + 1) % self.solving.as_ref().unwrap().vars.len();
+                self.prompt_solve(next);
+            },
+            SolveState::Solution => self.show_residual(),
+            SolveState::Residual => self.prompt_solve(0),
+        }
+    }
+
+    /// AC while solving, back to the equation.
+    pub(super) fn exit_solve(&mut self) {
+        let Some(run) = self.solving.take() else {
+            return;
+        };
+
+        self.clear();
+        self.user_input = run.equation;
+        self.cursor_at = self.user_input.len();
+    }
+
+    fn solve_error(&mut self, e: Error) {
+        self.exit_solve();
+        self.error = Some(e);
+    }
+
+    /// Stores the typed value in the variable prompted for, keeping the
+    /// previous one when nothing was typed.
+    fn take_solve_value(&mut self, at: usize) -> Result<(), Error> {
+        if self.user_input.is_empty() {
+            return Ok(());
+        }
+
+        let value = parser::parse(&self.user_input).and_then(|e| self.eval(&e))?;
+        let v = self.solving.as_ref().unwrap().vars[at];
+        self.set_variable(v, value);
+        Ok(())
+    }
+
+    fn prompt_solve(&mut self, at: usize) {
+        let run = self.solving.as_mut().unwrap();
+        run.state = SolveState::Prompt(at);
+        let v = run.vars[at];
+
+        self.clear();
+        self.label = Some(format!("{}?", v.name()));
+        self.result = Some(self.variable(v).clone());
+    }
+
+    fn solve_for(&mut self, v: Var) {
+        let guess = self.variable(v).clone();
+        let residual = self.solving.as_ref().unwrap().residual.clone();
+        let root = Solver::default().solve(
+            |x| {
+                self.set_variable(v, x.clone());
+                self.eval(&residual).ok()
+            },
+            guess.clone(),
+        );
+
+        let Some(root) = root else {
+            self.set_variable(v, guess);
+            return self.solve_error(Error::CantSolve);
+        };
+
+        self.set_variable(v, root.clone());
+        self.solving.as_mut().unwrap().state = SolveState::Solution;
+        self.clear();
+        self.label = Some(format!("{}=", v.name()));
+        self.result = Some(root);
+        self.fresh = true;
+    }
+
+    fn show_residual(&mut self) {
+        let residual = self.solving.as_ref().unwrap().residual.clone();
+        match self.eval(&residual) {
+            Ok(r) => {
+                self.solving.as_mut().unwrap().state = SolveState::Residual;
+                self.clear();
+                self.label = Some("L–R=".to_string());
+                self.result = Some(r);
+                self.fresh = true;
+            },
+            Err(e) => self.solve_error(e),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use fx_maths::{Float, stat::Point};
+
+use super::{Calculator, Error, parser};
+
+impl Calculator {
+    /// DT: adds the `x,y` pair in the input to the data.
+    pub(super) fn enter_data(&mut self) {
+        match self.input_point() {
+            Ok(p) => {
+                self.stat_data.push(p);
+                self.show_count();
+            },
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// CL: removes the `x,y` pair in the input from the data.
+    pub(super) fn clear_data(&mut self) {
+        match self.input_point() {
+            Ok(p) => {
+                if let Some(i) = self.stat_data.iter().rposition(|d| d.x == p.x && d.y == p.y) {
+                    self.stat_data.remove(i);
+                }
+
+                self.show_count();
+            },
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// The `x,y` pair in the input, or `x,y,freq` with frequency on.
+    fn input_point(&mut self) -> Result<Point, Error> {
+        let list = parser::parse_list(&self.user_input)?;
+        let (x, y, freq) = match &list[..] {
+            [x, y] => (x, y, None),
+            [x, y, freq] if self.settings.stat_frequency => (x, y, Some(freq)),
+            _ => return Err(Error::Syntax),
+        };
+
+        let freq = match freq {
+            Some(f) => self.eval(f)?,
+            None => Float::ONE,
+        };
+        if freq.is_negative() || freq.is_zero() {
+            return Err(Error::Math);
+        }
+
+        Ok(Point {
+            x: self.eval(x)?,
+            y: self.eval(y)?,
+            freq,
+        })
+    }
+
+    fn show_count(&mut self) {
+        self.user_input.clear();
+        self.cursor_at = 0;
+        self.label = Some("n=".to_string());
+        self.result = Some(Float::from_int(self.stat_data.len() as i64));
+        self.fresh = true;
+    }
+}
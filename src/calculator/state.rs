@@ -0,0 +1,341 @@
+//! Saving the calculator between runs. The state is a plain text file, one
+//! entry per line, starting with a header that carries the format version.
+//! Lines that cannot be read are skipped, so a damaged file loses only what
+//! is damaged.
+
+use std::{fs, io, path::PathBuf};
+
+use fx_maths::{Float, constants::CONSTANTS, conversion::CONVERSIONS, stat::{Point, Regression}, trig::AngleUnit};
+
+use super::{Base, Calculator, CONVERSION_NAMES, Mode, Token};
+use super::formula::{USER_FORMULAS, UserFormula};
+use super::program::PROGRAM_AREAS;
+use super::setup::{ComplexFormat, DisplayFormat, FractionFormat};
+use super::variable::Var;
+
+#[cfg(test)]
+mod tests;
+
+const HEADER: &str = "fx-50FH II state";
+/// Version of the format written, files of older versions are still read.
+const VERSION: u32 = 1;
+
+/// Why a state file was not read at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadError {
+    /// The header is missing, this is not a state file.
+    NotState,
+    /// Written by a newer version that this one does not understand.
+    Newer(u32),
+}
+
+const REGRESSIONS: [(&str, Regression); 7] = [
+    ("Lin", Regression::Linear),
+    ("Log", Regression::Logarithmic),
+    ("Exp", Regression::Exponential),
+    ("Pwr", Regression::Power),
+    ("Inv", Regression::Inverse),
+    ("Quad", Regression::Quadratic),
+    ("AB-Exp", Regression::AbExponential),
+];
+
+/// Where the state is kept, `$XDG_STATE_HOME/fx-50fh-ii/state` falling back
+/// to `~/.local/state`.
+pub fn state_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/state")))?;
+    Some(base.join("fx-50fh-ii").join("state"))
+}
+
+impl Calculator {
+    /// A calculator in the state saved by the last run. A file that is not
+    /// a state file is moved aside to `state.bak` rather than overwritten on
+    /// exit, one of a newer version is left alone and not written over.
+    pub fn restore() -> Self {
+        let Some(path) = state_path() else {
+            return Self::new();
+        };
+
+        fs::read_to_string(&path).map_or_else(|_| Self::new(), |text| {
+            match Self::load(&text) {
+                Ok(c) => c,
+                Err(LoadError::NotState) => {
+                    let _ = fs::rename(&path, path.with_extension("bak"));
+                    Self::new()
+                },
+                Err(LoadError::Newer(_)) => Self { persisting: false, ..Self::new() },
+            }
+        })
+    }
+
+    /// Whether `persist` writes the state, not when it was restored from a
+    /// newer version.
+    pub const fn persists(&self) -> bool {
+        self.persisting
+    }
+
+    /// Writes the state for the next run.
+    pub fn persist(&self) -> io::Result<()> {
+        let Some(path) = state_path().filter(|_| self.persisting) else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // written in full before replacing the old state
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, self.save())?;
+        fs::rename(temp, path)
+    }
+
+    /// The state as the text of a state file. Of the history only the last
+    /// calculation is kept, as the calculator keeps no other.
+    pub fn save(&self) -> String {
+        let s = &self.settings;
+        let mut lines = vec![format!("{HEADER} {VERSION}")];
+
+        lines.push(format!("angle {}", match s.angle {
+            AngleUnit::Degree => "Deg",
+            AngleUnit::Radian => "Rad",
+            AngleUnit::Gradian => "Gra",
+        }));
+        lines.push(match s.display {
+            DisplayFormat::Fix(n) => format!("display Fix {n}"),
+            DisplayFormat::Sci(n) => format!("display Sci {n}"),
+            DisplayFormat::Norm(n) => format!("display Norm {n}"),
+        });
+        lines.push(format!("fraction {}", match s.fraction {
+            FractionFormat::Mixed => "ab/c",
+            FractionFormat::Improper => "d/c",
+        }));
+        lines.push(format!("complex {}", match s.complex {
+            ComplexFormat::Rectangular => "a+bi",
+            ComplexFormat::Polar => "r∠θ",
+        }));
+        lines.push(format!("frequency {}", if s.stat_frequency { "on" } else { "off" }));
+        lines.push(format!("mode {}", save_mode(self.mode)));
+
+        for v in Var::ALL {
+            lines.push(format!("var {} {}", v.name(), self.variable(v)));
+        }
+        lines.push(format!("ans {}", self.answer));
+
+        // the last calculation, unless the input belongs to something else
+        if self.editing.is_none() && self.formula_edit.is_none() && !self.user_input.is_empty() {
+            lines.push(format!("input {}", save_tokens(&self.user_input)));
+        }
+
+        for (i, p) in self.programs.iter().enumerate() {
+            if !p.tokens.is_empty() {
+                lines.push(format!("program {} {} {}", i + 1, save_mode(p.mode), save_tokens(&p.tokens)));
+            }
+        }
+
+        for p in &self.stat_data {
+            lines.push(format!("data {} {} {}", p.x, p.y, p.freq));
+        }
+
+        for f in &self.user_formulas {
+            lines.push(format!("formula {} | {}", save_tokens(&f.name), save_tokens(&f.body)));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// A calculator in a saved state.
+    pub fn load(text: &str) -> Result<Self, LoadError> {
+        let mut lines = text.lines();
+        let version = lines.next()
+            .and_then(|h| h.strip_prefix(HEADER))
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .ok_or(LoadError::NotState)?;
+        if version > VERSION {
+            return Err(LoadError::Newer(version));
+        }
+
+        let mut c = Self::new();
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            // a line that does not read is left out
+            let _ = c.load_line(key, value);
+        }
+
+        Ok(c)
+    }
+
+    fn load_line(&mut self, key: &str, value: &str) -> Option<()> {
+        let s = &mut self.settings;
+        match key {
+            "angle" => s.angle = match value {
+                "Deg" => AngleUnit::Degree,
+                "Rad" => AngleUnit::Radian,
+                "Gra" => AngleUnit::Gradian,
+                _ => return None,
+            },
+            "display" => {
+                let (kind, n) = value.split_once(' ')?;
+                let format = match kind {
+                    "Fix" => DisplayFormat::Fix(0),
+                    "Sci" => DisplayFormat::Sci(0),
+                    "Norm" => DisplayFormat::Norm(1),
+                    _ => return None,
+                };
+                s.display = format.with_digits(n.parse().ok()?)?;
+            },
+            "fraction" => s.fraction = match value {
+                "ab/c" => FractionFormat::Mixed,
+                "d/c" => FractionFormat::Improper,
+                _ => return None,
+            },
+            "complex" => s.complex = match value {
+                "a+bi" => ComplexFormat::Rectangular,
+                "r∠θ" => ComplexFormat::Polar,
+                _ => return None,
+            },
+            "frequency" => s.stat_frequency = match value {
+                "on" => true,
+                "off" => false,
+                _ => return None,
+            },
+            "mode" => self.mode = load_mode(value)?,
+            "var" => {
+                let (name, x) = value.split_once(' ')?;
+                let v = Var::ALL.into_iter().find(|v| v.name() == name)?;
+                self.set_variable(v, x.parse().ok()?);
+            },
+            "ans" => self.answer = value.parse().ok()?,
+            "input" => {
+                self.user_input = load_tokens(value)?;
+                self.cursor_at = self.user_input.len();
+            },
+            "program" => {
+                let mut parts = value.splitn(3, ' ');
+                let slot = parts.next()?.parse::<usize>().ok()?.checked_sub(1).filter(|s| *s < PROGRAM_AREAS)?;
+                let mode = load_mode(parts.next()?)?;
+                let tokens = load_tokens(parts.next().unwrap_or(""))?;
+                if tokens.len() > self.remaining_steps() + self.programs[slot].tokens.len() {
+                    return None;
+                }
+
+                self.programs[slot].mode = mode;
+                self.programs[slot].tokens = tokens;
+            },
+            "data" => {
+                let mut parts = value.split(' ').map(str::parse::<Float>);
+                let (x, y, freq) = (parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?);
+                self.stat_data.push(Point { x, y, freq });
+            },
+            "formula" if self.user_formulas.len() < USER_FORMULAS => {
+                let (name, body) = value.split_once(" | ")?;
+                self.user_formulas.push(UserFormula {
+                    name: load_tokens(name)?,
+                    body: load_tokens(body)?,
+                });
+            },
+            _ => return None,
+        }
+
+        Some(())
+    }
+}
+
+fn save_mode(mode: Mode) -> String {
+    match mode {
+        Mode::Computation => "COMP".to_string(),
+        Mode::Complex => "CMPLX".to_string(),
+        Mode::Base(Base::Binary) => "BIN".to_string(),
+        Mode::Base(Base::Octal) => "OCT".to_string(),
+        Mode::Base(Base::Decimal) => "DEC".to_string(),
+        Mode::Base(Base::Hexadecimal) => "HEX".to_string(),
+        Mode::SingleStat => "SD".to_string(),
+        Mode::PairedStat(r) => {
+            let name = REGRESSIONS.iter().find(|(_, x)| *x == r).map_or("Lin", |(n, _)| n);
+            format!("REG:{name}")
+        },
+        Mode::Program => "PRGM".to_string(),
+    }
+}
+
+fn load_mode(s: &str) -> Option<Mode> {
+    Some(match s {
+        "COMP" => Mode::Computation,
+        "CMPLX" => Mode::Complex,
+        "BIN" => Mode::Base(Base::Binary),
+        "OCT" => Mode::Base(Base::Octal),
+        "DEC" => Mode::Base(Base::Decimal),
+        "HEX" => Mode::Base(Base::Hexadecimal),
+        "SD" => Mode::SingleStat,
+        "PRGM" => Mode::Program,
+        _ => {
+            let name = s.strip_prefix("REG:")?;
+            Mode::PairedStat(REGRESSIONS.iter().find(|(n, _)| *n == name)?.1)
+        },
+    })
+}
+
+/// Tokens separated by spaces. Constants and conversions are saved by
+/// their symbols so that they survive changes to the catalogues.
+fn save_tokens(tokens: &[Token]) -> String {
+    tokens.iter().map(save_token).collect::<Vec<_>>().join(" ")
+}
+
+/// The name a token is saved under, kept in step with [`load_token`].
+fn save_token(t: &Token) -> String {
+    use Token::*;
+    match t {
+        Variable(v) => return format!("var:{}", v.name()),
+        Constant(i) => return format!("const:{}", CONSTANTS[*i].symbol),
+        Convert(i) => return format!("conv:{}", CONVERSION_NAMES[*i]),
+        Power => "Power", SquareRoot => "SquareRoot", EPower => "EPower", E => "E", Fraction => "Fraction",
+        _0 => "0", _1 => "1", _2 => "2", _3 => "3", _4 => "4",
+        _5 => "5", _6 => "6", _7 => "7", _8 => "8", _9 => "9",
+        Add => "Add", Subtract => "Subtract", Multiply => "Multiply", Divide => "Divide", Negative => "Negative",
+        TenPower => "TenPower", Dot => "Dot", Exp => "Exp",
+        BracketStart => "BracketStart", BracketEnd => "BracketEnd", Ans => "Ans", Comma => "Comma",
+        StatA => "StatA", StatB => "StatB", StatC => "StatC", StatR => "StatR",
+        XHat => "XHat", XHat1 => "XHat1", XHat2 => "XHat2", YHat => "YHat",
+        Colon => "Colon", Display => "Display", Store => "Store", Prompt => "Prompt",
+        Label => "Label", Goto => "Goto", Then => "Then", Isz => "Isz", Dsz => "Dsz",
+        Equal => "Equal", NotEqual => "NotEqual", Greater => "Greater",
+        GreaterEqual => "GreaterEqual", Less => "Less", LessEqual => "LessEqual",
+        Integral => "Integral", Derivative => "Derivative", Sum => "Sum", Product => "Product",
+        Factorial => "Factorial", Permutations => "Permutations", Combinations => "Combinations",
+        Random => "Random", RandomInt => "RandomInt", Pol => "Pol", Rec => "Rec",
+    }.to_string()
+}
+
+fn load_token(name: &str) -> Option<Token> {
+    use Token::*;
+    Some(match name.split_once(':') {
+        Some(("var", v)) => Variable(Var::ALL.into_iter().find(|x| x.name() == v)?),
+        Some(("const", c)) => Constant(CONSTANTS.iter().position(|x| x.symbol == c)?),
+        Some(("conv", c)) => Convert((0..CONVERSIONS.len()).find(|i| CONVERSION_NAMES[*i] == c)?),
+        _ => match name {
+            "Power" => Power, "SquareRoot" => SquareRoot, "EPower" => EPower, "E" => E, "Fraction" => Fraction,
+            "0" => _0, "1" => _1, "2" => _2, "3" => _3, "4" => _4,
+            "5" => _5, "6" => _6, "7" => _7, "8" => _8, "9" => _9,
+            "Add" => Add, "Subtract" => Subtract, "Multiply" => Multiply, "Divide" => Divide, "Negative" => Negative,
+            "TenPower" => TenPower, "Dot" => Dot, "Exp" => Exp,
+            "BracketStart" => BracketStart, "BracketEnd" => BracketEnd, "Ans" => Ans, "Comma" => Comma,
+            "StatA" => StatA, "StatB" => StatB, "StatC" => StatC, "StatR" => StatR,
+            "XHat" => XHat, "XHat1" => XHat1, "XHat2" => XHat2, "YHat" => YHat,
+            "Colon" => Colon, "Display" => Display, "Store" => Store, "Prompt" => Prompt,
+            "Label" => Label, "Goto" => Goto, "Then" => Then, "Isz" => Isz, "Dsz" => Dsz,
+            "Equal" => Equal, "NotEqual" => NotEqual, "Greater" => Greater,
+            "GreaterEqual" => GreaterEqual, "Less" => Less, "LessEqual" => LessEqual,
+            "Integral" => Integral, "Derivative" => Derivative, "Sum" => Sum, "Product" => Product,
+            "Factorial" => Factorial, "Permutations" => Permutations, "Combinations" => Combinations,
+            "Random" => Random, "RandomInt" => RandomInt, "Pol" => Pol, "Rec" => Rec,
+            _ => return None,
+        },
+    })
+}
+
+/// Tokens saved by [`save_tokens`], `None` if any is not known.
+fn load_tokens(s: &str) -> Option<Vec<Token>> {
+    s.split(' ').filter(|n| !n.is_empty()).map(load_token).collect()
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+fn float(s: &str) -> Float {
+    s.parse().unwrap()
+}
+
+#[test]
+fn round_trip() {
+    use Token::*;
+
+    let mut c = Calculator::new();
+    c.settings.angle = AngleUnit::Radian;
+    c.settings.display = DisplayFormat::Fix(3);
+    c.settings.fraction = FractionFormat::Improper;
+    c.settings.complex = ComplexFormat::Polar;
+    c.settings.stat_frequency = true;
+    c.mode = Mode::PairedStat(Regression::Quadratic);
+    c.set_variable(Var::A, float("15e-1"));
+    c.set_variable(Var::Y, float("-2"));
+    c.answer = float("42");
+    c.user_input = vec![_1, Add, Variable(Var::A), Multiply, Constant(0)];
+    c.programs[1].mode = Mode::Base(Base::Decimal);
+    c.programs[1].tokens = vec![Prompt, Store, Variable(Var::X), Colon, Convert(3), Display];
+    c.stat_data = vec![
+        Point { x: float("1"), y: float("2"), freq: float("1") },
+        Point { x: float("3"), y: float("-4e-1"), freq: float("2") },
+    ];
+    c.user_formulas = vec![UserFormula { name: vec![Variable(Var::E)], body: vec![Variable(Var::M), Multiply, _2] }];
+
+    let text = c.save();
+    let l = Calculator::load(&text).unwrap();
+    assert_eq!(l.save(), text);
+
+    assert_eq!(l.settings, c.settings);
+    assert_eq!(l.mode, c.mode);
+    assert_eq!(l.variable(Var::A), &float("15e-1"));
+    assert_eq!(l.variable(Var::Y), &float("-2"));
+    assert_eq!(l.answer, float("42"));
+    assert_eq!(l.user_input, c.user_input);
+    assert_eq!(l.cursor_at, 5);
+    assert_eq!(l.programs[1].mode, c.programs[1].mode);
+    assert_eq!(l.programs[1].tokens, c.programs[1].tokens);
+    assert!(l.programs[0].tokens.is_empty());
+    assert_eq!(l.stat_data, c.stat_data);
+    assert_eq!(l.user_formulas.len(), 1);
+    assert_eq!(l.user_formulas[0].name, c.user_formulas[0].name);
+    assert_eq!(l.user_formulas[0].body, c.user_formulas[0].body);
+}
+
+#[test]
+fn damaged() {
+    assert_eq!(Calculator::load("").err(), Some(LoadError::NotState));
+    assert_eq!(Calculator::load("angle Rad\n").err(), Some(LoadError::NotState));
+    assert_eq!(Calculator::load("fx-50FH II state x\n").err(), Some(LoadError::NotState));
+
+    // bad lines are skipped, the rest is kept
+    let c = Calculator::load(&format!(
+        "{HEADER} {VERSION}\nangle Sideways\nvar A 7\nvar Q 1\nvar B x\nprogram 9 COMP 1\ndata 1\ninput 1 nothing\nfrequency on\n",
+    )).unwrap();
+    assert_eq!(c.settings.angle, AngleUnit::Degree);
+    assert_eq!(c.variable(Var::A), &float("7"));
+    assert_eq!(c.variable(Var::B), &Float::ZERO);
+    assert!(c.programs.iter().all(|p| p.tokens.is_empty()));
+    assert!(c.stat_data.is_empty());
+    assert!(c.user_input.is_empty());
+    assert!(c.settings.stat_frequency);
+}
+
+#[test]
+fn versions() {
+    assert_eq!(Calculator::load(&format!("{HEADER} {}\n", VERSION + 1)).err(), Some(LoadError::Newer(VERSION + 1)));
+    assert!(Calculator::load(&format!("{HEADER} {VERSION}\n")).is_ok());
+    assert!(Calculator::load(&format!("{HEADER} 0\nvar A 1\n")).is_ok_and(|c| c.variable(Var::A) == &Float::ONE));
+}
+
+#[test]
+fn tokens() {
+    use Token::*;
+
+    let tokens = vec![
+        _0, Dot, Exp, Negative, XHat1, YHat, StatR, Then, LessEqual, Isz, Integral, RandomInt, Rec,
+        Variable(Var::M), Constant(CONSTANTS.len() - 1), Convert(CONVERSIONS.len() - 1),
+    ];
+    assert_eq!(load_tokens(&save_tokens(&tokens)), Some(tokens));
+    assert_eq!(load_tokens("Add Plus"), None);
+}
+
+#[test]
+fn restore() {
+    // the only test that reads the environment, so setting it is safe
+    let dir = std::env::temp_dir().join(format!("fx-50fh-ii-state-{}", std::process::id()));
+    std::env::set_var("XDG_STATE_HOME", &dir);
+    let path = state_path().unwrap();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+    // a newer file is neither moved nor written over
+    let newer = format!("{HEADER} {}\nvar A 1\n", VERSION + 1);
+    fs::write(&path, &newer).unwrap();
+    let c = Calculator::restore();
+    assert!(!c.persists());
+    c.persist().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), newer);
+
+    // one that is not a state file is moved aside
+    fs::write(&path, "not a state\n").unwrap();
+    let c = Calculator::restore();
+    assert!(c.persists());
+    assert_eq!(fs::read_to_string(path.with_extension("bak")).unwrap(), "not a state\n");
+    c.persist().unwrap();
+    assert!(Calculator::load(&fs::read_to_string(&path).unwrap()).is_ok());
+
+    fs::remove_dir_all(dir).unwrap();
+}
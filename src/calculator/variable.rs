@@ -0,0 +1,102 @@
+use fx_maths::Float;
+
+use super::{Calculator, Key, Token, parser};
+
+/// Variable memories A–F, M, X and Y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Var {
+    A, B, C, D, E, F, M, X, Y,
+}
+
+pub const VARIABLES: usize = 9;
+
+impl Var {
+    pub const ALL: [Self; VARIABLES] = [Self::A, Self::B, Self::C, Self::D, Self::E, Self::F, Self::M, Self::X, Self::Y];
+
+    /// Variable typed by pressing ALPHA and this key.
+    pub const fn from_key(k: &Key) -> Option<Self> {
+        Some(match k {
+            Key::Negative => Self::A,
+            Key::Base60 => Self::B,
+            Key::Hyp => Self::C,
+            Key::Sin => Self::D,
+            Key::Cos => Self::E,
+            Key::Tan => Self::F,
+            Key::MPlus => Self::M,
+            Key::BracketEnd => Self::X,
+            Key::Comma => Self::Y,
+            _ => return None,
+        })
+    }
+
+    pub const fn name(self) -> &'static str {
+        use Var::*;
+        match self {
+            A => "A", B => "B", C => "C", D => "D", E => "E",
+            F => "F", M => "M", X => "X", Y => "Y",
+        }
+    }
+}
+
+/// Variables used in some input, in the order they first appear.
+pub fn variables_in(tokens: &[Token]) -> Vec<Var> {
+    let mut vars = Vec::new();
+    for t in tokens {
+        if let Token::Variable(v) = t {
+            if !vars.contains(v) {
+                vars.push(*v);
+            }
+        }
+    }
+
+    vars
+}
+
+impl Calculator {
+    pub const fn variable(&self, v: Var) -> &Float {
+        &self.variables[v as usize]
+    }
+
+    pub(super) const fn set_variable(&mut self, v: Var, value: Float) {
+        self.variables[v as usize] = value;
+    }
+
+    /// STO: stores the input, or Ans if there is none, into a variable. In
+    /// the program editor this types `→` instead.
+    pub(super) fn store(&mut self, v: Var) {
+        if self.editing.is_some() {
+            self.insert(Token::Store);
+            self.insert(Token::Variable(v));
+            return;
+        }
+
+        let value = if self.user_input.is_empty() || self.fresh {
+            Ok(self.answer.clone())
+        } else {
+            parser::parse(&self.user_input).and_then(|e| self.eval(&e))
+        };
+
+        match value {
+            Ok(value) => {
+                self.set_variable(v, value.clone());
+                self.answer = value;
+                self.show_variable(v);
+            },
+            Err(e) => self.error = Some(e),
+        }
+        self.modifier_key = None;
+    }
+
+    /// RCL: shows the value of a variable.
+    pub(super) fn recall(&mut self, v: Var) {
+        self.show_variable(v);
+        self.modifier_key = None;
+    }
+
+    fn show_variable(&mut self, v: Var) {
+        self.clear();
+        self.label = Some(format!("{}=", v.name()));
+        self.result = Some(self.variable(v).clone());
+        self.fresh = true;
+    }
+}
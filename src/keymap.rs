@@ -0,0 +1,448 @@
+//! Which keyboard key presses which calculator keys. One of the built-in
+//! layouts is the base, a `keymap.toml` in the config directory can choose
+//! it with `layout = "natural"` and change it with lines like `"l" = "Add"`
+//! or `"!" = "Shift PowNegOne"`.
+
+use std::{collections::HashMap, fmt, fs, path::PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use fx_50fh_ii::{KEY_NAMES, Key};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    keys: HashMap<(KeyCode, KeyModifiers), Vec<Key>>,
+}
+
+/// Something wrong with a keymap file, with its line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    Syntax(usize),
+    UnknownLayout(usize, String),
+    UnknownInput(usize, String),
+    UnknownKey(usize, String),
+    /// The input is bound to another key earlier in the file.
+    Conflict(usize, String),
+    /// Reserved for leaving the calculator.
+    Reserved(usize),
+    /// Cannot be pressed on its own, its input in the layout is taken.
+    Unmapped(Key),
+}
+
+/// A built-in layout, the base that a keymap file changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// The layout of `button_map.md`, following the rows of the calculator
+    /// over a QWERTY keyboard.
+    #[default]
+    QwertyGrid,
+    /// Keys that do what they show, letters typing variables.
+    Natural,
+    /// Numbers on the numeric keypad, the other keys in rows of six on the
+    /// left of the keyboard.
+    Numpad,
+}
+
+const LAYOUTS: &[(&str, Layout)] = &[
+    ("qwerty-grid", Layout::QwertyGrid),
+    ("natural", Layout::Natural),
+    ("numpad", Layout::Numpad),
+];
+
+type Binding = (KeyCode, &'static [Key]);
+
+const QWERTY_GRID: &[Binding] = {
+    use KeyCode::*;
+    &[
+        (Char('`'), &[Key::Shift]),
+        (Char('0'), &[Key::_0]),
+        (Char('1'), &[Key::_1]),
+        (Char('2'), &[Key::_2]),
+        (Char('3'), &[Key::_3]),
+        (Char('4'), &[Key::_4]),
+        (Char('5'), &[Key::_5]),
+        (Char('6'), &[Key::_6]),
+        (Char('7'), &[Key::_7]),
+        (Char('8'), &[Key::_8]),
+        (Char('9'), &[Key::_9]),
+        (Char('-'), &[Key::Subtract]),
+        (Char('='), &[Key::Alpha]),
+        (Enter, &[Key::Exe]),
+        (Backspace, &[Key::Del]),
+
+        (Char('q'), &[Key::Prog]),
+        (Char('w'), &[Key::Fmla]),
+        (Char('e'), &[Key::Exp]),
+        (Char('t'), &[Key::PowNegOne]),
+        (Char('y'), &[Key::Cubed]),
+        (Char('u'), &[Key::Rcl]),
+        (Char('i'), &[Key::Eng]),
+        (Char('o'), &[Key::BracketStart]),
+        (Char('p'), &[Key::BracketEnd]),
+        (Char(']'), &[Key::MPlus]),
+        (Char('\\'), &[Key::Mode]),
+
+        (Char('a'), &[Key::Fraction]),
+        (Char('s'), &[Key::SquareRoot]),
+        (Char('d'), &[Key::Squared]),
+        (Char('f'), &[Key::Power]),
+        (Char('g'), &[Key::Log]),
+        (Char('h'), &[Key::Ln]),
+        (Char('k'), &[Key::Ac]),
+        (Char('l'), &[Key::Add]),
+        (Char(';'), &[Key::Multiply]),
+
+        (Char('z'), &[Key::Negative]),
+        (Char('x'), &[Key::Base60]),
+        (Char('c'), &[Key::Hyp]),
+        (Char('v'), &[Key::Sin]),
+        (Char('b'), &[Key::Cos]),
+        (Char('n'), &[Key::Tan]),
+        (Char('m'), &[Key::Ans]),
+        (Char(','), &[Key::Comma]),
+        (Char('.'), &[Key::Dot]),
+        (Char('/'), &[Key::Divide]),
+
+        (Left, &[Key::Left]),
+        (Down, &[Key::Down]),
+        (Up, &[Key::Up]),
+        (Right, &[Key::Right]),
+    ]
+};
+
+/// Digits, operators and cursor keys where they are on any keyboard, part
+/// of the other layouts.
+const COMMON: &[Binding] = {
+    use KeyCode::*;
+    &[
+        (Char('0'), &[Key::_0]),
+        (Char('1'), &[Key::_1]),
+        (Char('2'), &[Key::_2]),
+        (Char('3'), &[Key::_3]),
+        (Char('4'), &[Key::_4]),
+        (Char('5'), &[Key::_5]),
+        (Char('6'), &[Key::_6]),
+        (Char('7'), &[Key::_7]),
+        (Char('8'), &[Key::_8]),
+        (Char('9'), &[Key::_9]),
+        (Char('.'), &[Key::Dot]),
+        (Char('+'), &[Key::Add]),
+        (Char('-'), &[Key::Subtract]),
+        (Char('*'), &[Key::Multiply]),
+        (Char('/'), &[Key::Divide]),
+        (Char('('), &[Key::BracketStart]),
+        (Char(')'), &[Key::BracketEnd]),
+        (Char(','), &[Key::Comma]),
+        (Enter, &[Key::Exe]),
+        (Backspace, &[Key::Del]),
+        (Delete, &[Key::Ac]),
+        (Insert, &[Key::Shift, Key::Del]),
+
+        (Left, &[Key::Left]),
+        (Down, &[Key::Down]),
+        (Up, &[Key::Up]),
+        (Right, &[Key::Right]),
+    ]
+};
+
+const NATURAL: &[Binding] = {
+    use KeyCode::*;
+    &[
+        (Char('='), &[Key::Exe]),
+        (Char('^'), &[Key::Power]),
+        (Char('!'), &[Key::Shift, Key::PowNegOne]),
+        (Char('_'), &[Key::Negative]),
+        (Char('\''), &[Key::Base60]),
+        (Char('#'), &[Key::MPlus]),
+        (Tab, &[Key::Shift]),
+        (Char('`'), &[Key::Alpha]),
+        (Char('\\'), &[Key::Mode]),
+
+        (Char('a'), &[Key::Alpha, Key::Negative]),
+        (Char('b'), &[Key::Alpha, Key::Base60]),
+        (Char('c'), &[Key::Alpha, Key::Hyp]),
+        (Char('d'), &[Key::Alpha, Key::Sin]),
+        (Char('e'), &[Key::Alpha, Key::Cos]),
+        (Char('f'), &[Key::Alpha, Key::Tan]),
+        (Char('m'), &[Key::Alpha, Key::MPlus]),
+        (Char('x'), &[Key::Alpha, Key::BracketEnd]),
+        (Char('y'), &[Key::Alpha, Key::Comma]),
+        (Char('A'), &[Key::Alpha, Key::Negative]),
+        (Char('B'), &[Key::Alpha, Key::Base60]),
+        (Char('C'), &[Key::Alpha, Key::Hyp]),
+        (Char('D'), &[Key::Alpha, Key::Sin]),
+        (Char('E'), &[Key::Alpha, Key::Cos]),
+        (Char('F'), &[Key::Alpha, Key::Tan]),
+        (Char('M'), &[Key::Alpha, Key::MPlus]),
+        (Char('X'), &[Key::Alpha, Key::BracketEnd]),
+        (Char('Y'), &[Key::Alpha, Key::Comma]),
+
+        (Char('s'), &[Key::Sin]),
+        (Char('o'), &[Key::Cos]),
+        (Char('t'), &[Key::Tan]),
+        (Char('h'), &[Key::Hyp]),
+        (Char('l'), &[Key::Log]),
+        (Char('n'), &[Key::Ln]),
+        (Char('r'), &[Key::SquareRoot]),
+        (Char('q'), &[Key::Squared]),
+        (Char('u'), &[Key::Cubed]),
+        (Char('i'), &[Key::PowNegOne]),
+        (Char('k'), &[Key::Fraction]),
+        (Char('j'), &[Key::Exp]),
+        (Char('z'), &[Key::Ans]),
+        (Char('g'), &[Key::Eng]),
+        (Char('v'), &[Key::Rcl]),
+        (Char('p'), &[Key::Prog]),
+        (Char('w'), &[Key::Fmla]),
+    ]
+};
+
+const NUMPAD: &[Binding] = {
+    use KeyCode::*;
+    &[
+        (Char('q'), &[Key::Shift]),
+        (Char('w'), &[Key::Alpha]),
+        (Char('e'), &[Key::Mode]),
+        (Char('r'), &[Key::Prog]),
+        (Char('t'), &[Key::Fmla]),
+        (Char('y'), &[Key::PowNegOne]),
+
+        (Char('a'), &[Key::Cubed]),
+        (Char('s'), &[Key::Fraction]),
+        (Char('d'), &[Key::SquareRoot]),
+        (Char('f'), &[Key::Squared]),
+        (Char('g'), &[Key::Power]),
+        (Char('h'), &[Key::Log]),
+
+        (Char('z'), &[Key::Ln]),
+        (Char('x'), &[Key::Negative]),
+        (Char('c'), &[Key::Base60]),
+        (Char('v'), &[Key::Hyp]),
+        (Char('b'), &[Key::Sin]),
+        (Char('n'), &[Key::Cos]),
+
+        (Char('u'), &[Key::Tan]),
+        (Char('i'), &[Key::Rcl]),
+        (Char('o'), &[Key::Eng]),
+        (Char('p'), &[Key::MPlus]),
+        (Char('j'), &[Key::Exp]),
+        (Char('k'), &[Key::Ans]),
+    ]
+};
+
+impl Layout {
+    pub fn from_name(name: &str) -> Option<Self> {
+        LAYOUTS.iter().find(|(n, _)| *n == name).map(|(_, l)| *l)
+    }
+
+    fn bindings(self) -> impl Iterator<Item = &'static Binding> {
+        let (common, own) = match self {
+            Self::QwertyGrid => (&[][..], QWERTY_GRID),
+            Self::Natural => (COMMON, NATURAL),
+            Self::Numpad => (COMMON, NUMPAD),
+        };
+        common.iter().chain(own)
+    }
+}
+
+/// Keyboard keys with names, the others are written as the character.
+const INPUT_NAMES: &[(&str, KeyCode)] = {
+    use KeyCode::*;
+    &[
+        ("Enter", Enter), ("Backspace", Backspace), ("Tab", Tab), ("Space", Char(' ')),
+        ("Left", Left), ("Right", Right), ("Up", Up), ("Down", Down),
+        ("Home", Home), ("End", End), ("PageUp", PageUp), ("PageDown", PageDown),
+        ("Delete", Delete), ("Insert", Insert),
+    ]
+};
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Layout::default())
+    }
+}
+
+/// Where the keymap is read from, `$XDG_CONFIG_HOME/fx-50fh-ii/keymap.toml`
+/// falling back to `~/.config`.
+pub fn keymap_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(base.join("fx-50fh-ii").join("keymap.toml"))
+}
+
+impl Keymap {
+    pub fn preset(layout: Layout) -> Self {
+        Self {
+            keys: layout.bindings().map(|(c, k)| ((*c, KeyModifiers::NONE), k.to_vec())).collect(),
+        }
+    }
+
+    /// The keymap in the config directory, the layout alone if there is none.
+    /// A `layout` given here takes precedence over the one in the file.
+    pub fn load(layout: Option<Layout>) -> (Self, Vec<Problem>) {
+        keymap_path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map_or_else(|| (Self::preset(layout.unwrap_or_default()), Vec::new()), |text| Self::parse(&text, layout))
+    }
+
+    /// Reads a keymap file. With any errors in it the layout is used alone,
+    /// inputs the file leaves out keep what they press in the layout unless
+    /// the file has moved that elsewhere.
+    pub fn parse(text: &str, layout: Option<Layout>) -> (Self, Vec<Problem>) {
+        let mut keys = HashMap::new();
+        let mut chosen = None;
+        let mut problems = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line == "[keys]" {
+                continue;
+            }
+
+            let n = i + 1;
+            let Some((input, value)) = parse_line(line) else {
+                problems.push(Problem::Syntax(n));
+                continue;
+            };
+
+            if input == "layout" {
+                match Layout::from_name(&value) {
+                    Some(l) => chosen = Some(l),
+                    None => problems.push(Problem::UnknownLayout(n, value)),
+                }
+                continue;
+            }
+
+            let Some(code) = parse_input(&input) else {
+                problems.push(Problem::UnknownInput(n, input));
+                continue;
+            };
+            if code.0 == KeyCode::Esc {
+                problems.push(Problem::Reserved(n));
+                continue;
+            }
+
+            let sequence: Option<Vec<_>> = value.split_whitespace().map(Key::from_name).collect();
+            let Some(sequence) = sequence.filter(|s| !s.is_empty()) else {
+                problems.push(Problem::UnknownKey(n, value));
+                continue;
+            };
+            if keys.insert(code, sequence).is_some() {
+                problems.push(Problem::Conflict(n, input));
+            }
+        }
+
+        let layout = layout.or(chosen).unwrap_or_default();
+        if !problems.is_empty() {
+            return (Self::preset(layout), problems);
+        }
+
+        for (code, sequence) in layout.bindings() {
+            if keys.values().any(|s| s == sequence) {
+                continue;
+            }
+
+            keys.entry((*code, KeyModifiers::NONE)).or_insert_with(|| sequence.to_vec());
+        }
+
+        for (_, key) in KEY_NAMES {
+            if !keys.values().any(|s| s == &[*key]) {
+                problems.push(Problem::Unmapped(*key));
+            }
+        }
+
+        (Self { keys }, problems)
+    }
+
+    /// The calculator keys pressed by a key event, in order.
+    pub fn get(&self, ke: &KeyEvent) -> &[Key] {
+        if ke.kind != KeyEventKind::Press {
+            return &[];
+        }
+
+        // shifted characters come as different characters already
+        let modifiers = ke.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        self.keys.get(&(ke.code, modifiers)).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// `"input" = "Key"`, the input may also be left unquoted.
+fn parse_line(line: &str) -> Option<(String, String)> {
+    let (input, rest) = match line.strip_prefix('"') {
+        Some(quoted) => parse_string(quoted)?,
+        None => {
+            let eq = line.find('=')?;
+            (line[..eq].trim().to_string(), &line[eq..])
+        },
+    };
+
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let (key, rest) = parse_string(rest.strip_prefix('"')?)?;
+    let rest = rest.trim();
+    (rest.is_empty() || rest.starts_with('#')).then_some((input, key))
+}
+
+/// The contents of a string after its opening quote, and what follows it.
+fn parse_string(s: &str) -> Option<(String, &str)> {
+    let mut out = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &s[i + 1..])),
+            '\\' => out.push(match chars.next()?.1 {
+                '\\' => '\\',
+                '"' => '"',
+                _ => return None,
+            }),
+            c => out.push(c),
+        }
+    }
+
+    None
+}
+
+/// `Ctrl+Alt+x`, a keyboard key after any number of modifiers.
+fn parse_input(input: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = input;
+    // a lone `+` is the key itself
+    while let Some((m, r)) = rest.split_once('+').filter(|(m, r)| !m.is_empty() && !r.is_empty()) {
+        modifiers |= match m {
+            "Ctrl" => KeyModifiers::CONTROL,
+            "Alt" => KeyModifiers::ALT,
+            _ => return None,
+        };
+        rest = r;
+    }
+
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ if rest == "Esc" => KeyCode::Esc,
+        _ => match rest.strip_prefix('F').and_then(|n| n.parse().ok()) {
+            Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+            _ => INPUT_NAMES.iter().find(|(n, _)| *n == rest)?.1,
+        },
+    };
+
+    Some((code, modifiers))
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Syntax(n) => write!(f, "line {n}: expected `\"input\" = \"Key\"`"),
+            Self::UnknownLayout(n, s) => write!(f, "line {n}: unknown layout `{s}`"),
+            Self::UnknownInput(n, s) => write!(f, "line {n}: unknown keyboard key `{s}`"),
+            Self::UnknownKey(n, s) => write!(f, "line {n}: unknown calculator key `{s}`"),
+            Self::Conflict(n, s) => write!(f, "line {n}: `{s}` is already bound"),
+            Self::Reserved(n) => write!(f, "line {n}: Esc is reserved for quitting"),
+            Self::Unmapped(k) => write!(f, "{} is not bound to any key", k.name()),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::*;
+
+fn press(keymap: &Keymap, code: KeyCode, modifiers: KeyModifiers) -> Vec<Key> {
+    keymap.get(&KeyEvent::new(code, modifiers)).to_vec()
+}
+
+fn char(keymap: &Keymap, c: char) -> Vec<Key> {
+    press(keymap, KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+#[test]
+fn comments() {
+    let (keymap, problems) = Keymap::parse("# a keymap\n\n[keys]\n  \n\"l\" = \"Sin\" # was Add\n\"v\" = \"Add\"\n", None);
+    assert_eq!(problems, vec![]);
+    assert_eq!(char(&keymap, 'l'), vec![Key::Sin]);
+    assert_eq!(char(&keymap, 'v'), vec![Key::Add]);
+}
+
+#[test]
+fn inputs() {
+    let text = "\"Ctrl+a\" = \"Ac\"\n\"Alt+Ctrl+F5\" = \"Mode\"\n\"+\" = \"Add\"\n\"Ctrl++\" = \"Subtract\"\nEnter = \"Exe\"\n";
+    let (keymap, problems) = Keymap::parse(text, None);
+    assert_eq!(problems, vec![]);
+    assert_eq!(press(&keymap, KeyCode::Char('a'), KeyModifiers::CONTROL), vec![Key::Ac]);
+    assert_eq!(press(&keymap, KeyCode::F(5), KeyModifiers::CONTROL | KeyModifiers::ALT), vec![Key::Mode]);
+    assert_eq!(char(&keymap, '+'), vec![Key::Add]);
+    assert_eq!(press(&keymap, KeyCode::Char('+'), KeyModifiers::CONTROL), vec![Key::Subtract]);
+    assert_eq!(press(&keymap, KeyCode::Enter, KeyModifiers::NONE), vec![Key::Exe]);
+    // SHIFT comes with the shifted character itself
+    assert_eq!(press(&keymap, KeyCode::Char('+'), KeyModifiers::SHIFT), vec![Key::Add]);
+}
+
+#[test]
+fn problems() {
+    let (_, problems) = Keymap::parse("\"l\" = \"Add\"\n\"l\" = \"Sin\"\n", None);
+    assert_eq!(problems, vec![Problem::Conflict(2, "l".to_string())]);
+
+    let (_, problems) = Keymap::parse("\"Esc\" = \"Ac\"\n", None);
+    assert_eq!(problems, vec![Problem::Reserved(1)]);
+
+    let (_, problems) = Keymap::parse("\"l\" = Add\n\"Hyper+x\" = \"Add\"\n\"x\" = \"Plus\"\nlayout = \"dvorak\"\n", None);
+    assert_eq!(problems, vec![
+        Problem::Syntax(1),
+        Problem::UnknownInput(2, "Hyper+x".to_string()),
+        Problem::UnknownKey(3, "Plus".to_string()),
+        Problem::UnknownLayout(4, "dvorak".to_string()),
+    ]);
+
+    // l pressed Add, which now has no key of its own; only a warning, the file is kept
+    let (keymap, problems) = Keymap::parse("\"l\" = \"Shift Add\"\n", None);
+    assert_eq!(problems, vec![Problem::Unmapped(Key::Add)]);
+    assert_eq!(char(&keymap, 'l'), vec![Key::Shift, Key::Add]);
+}
+
+#[test]
+fn errors_keep_the_layout() {
+    let (keymap, problems) = Keymap::parse("\"l\" = \"Sin\"\n\"v\" = \"Add\"\n\"Esc\" = \"Ac\"\n", None);
+    assert_eq!(problems, vec![Problem::Reserved(3)]);
+    assert_eq!(char(&keymap, 'l'), vec![Key::Add]);
+    assert_eq!(keymap.keys, Keymap::preset(Layout::QwertyGrid).keys);
+}
+
+#[test]
+fn layouts() {
+    let natural = Keymap::preset(Layout::Natural);
+    assert_eq!(char(&natural, '!'), vec![Key::Shift, Key::PowNegOne]);
+
+    // every key can be pressed on its own in every layout
+    for (name, layout) in LAYOUTS {
+        let keymap = Keymap::preset(*layout);
+        for (_, key) in KEY_NAMES {
+            assert!(keymap.keys.values().any(|s| s == &[*key]), "{} has no key in {name}", key.name());
+        }
+        assert_eq!(Keymap::parse("", Some(*layout)).1, vec![]);
+    }
+}
+
+#[test]
+fn layout_option() {
+    // --layout numpad over the file's natural
+    let (keymap, problems) = Keymap::parse("layout = \"natural\"\n", Some(Layout::Numpad));
+    assert_eq!(problems, vec![]);
+    assert_eq!(char(&keymap, 'q'), vec![Key::Shift]);
+    assert_eq!(keymap.keys, Keymap::preset(Layout::Numpad).keys);
+
+    let (keymap, _) = Keymap::parse("layout = \"natural\"\n", None);
+    assert_eq!(char(&keymap, 'q'), vec![Key::Squared]);
+}
@@ -0,0 +1,166 @@
+//! The face of the calculator drawn under the display, every key with what
+//! it does after SHIFT and ALPHA above it. Keys can be clicked.
+
+use std::io::{self, Write};
+
+use crossterm::{cursor, queue, style::{self, Color, Stylize}};
+
+use fx_50fh_ii::Key;
+
+#[cfg(test)]
+mod tests;
+
+/// Columns taken by the keypad, shared out between the keys of each row.
+const WIDTH: usize = 55;
+/// Lines taken by each row of keys, the legends and the key itself.
+const ROW_HEIGHT: u16 = 2;
+
+/// A key on the face, with its legends for SHIFT and ALPHA.
+struct Face {
+    key: Key,
+    label: &'static str,
+    shift: &'static str,
+    alpha: &'static str,
+}
+
+const fn face(key: Key, label: &'static str, shift: &'static str, alpha: &'static str) -> Face {
+    Face { key, label, shift, alpha }
+}
+
+const ROWS: &[&[Face]] = &[
+    &[
+        face(Key::Shift, "SHIFT", "", ""),
+        face(Key::Alpha, "ALPHA", "", ""),
+        face(Key::Left, "◀", "", ""),
+        face(Key::Up, "▲", "", ""),
+        face(Key::Down, "▼", "", ""),
+        face(Key::Right, "▶", "", ""),
+        face(Key::Mode, "MODE", "SETUP", ""),
+    ],
+    &[
+        face(Key::Prog, "PROG", "CALC", ""),
+        face(Key::Fmla, "FMLA", "SOLVE", "="),
+        face(Key::PowNegOne, "x⁻¹", "x!", ""),
+        face(Key::Cubed, "x³", "", ""),
+    ],
+    &[
+        face(Key::Fraction, "ab/c", "", ""),
+        face(Key::SquareRoot, "√", "d/dx", ""),
+        face(Key::Squared, "x²", "∫", ""),
+        face(Key::Power, "^", "", ""),
+        face(Key::Log, "log", "10ˣ", ""),
+        face(Key::Ln, "ln", "eˣ", "e"),
+    ],
+    &[
+        face(Key::Negative, "(-)", "", "A"),
+        face(Key::Base60, "°’”", "", "B"),
+        face(Key::Hyp, "hyp", "", "C"),
+        face(Key::Sin, "sin", "", "D"),
+        face(Key::Cos, "cos", "", "E"),
+        face(Key::Tan, "tan", "", "F"),
+    ],
+    &[
+        face(Key::Rcl, "RCL", "STO", ""),
+        face(Key::Eng, "ENG", "", ""),
+        face(Key::BracketStart, "(", "Σ", ""),
+        face(Key::BracketEnd, ")", "Π", "X"),
+        face(Key::Comma, ",", "", "Y"),
+        face(Key::MPlus, "M+", "CL", "M"),
+    ],
+    &[
+        face(Key::_7, "7", "CONST", ""),
+        face(Key::_8, "8", "CONV", ""),
+        face(Key::_9, "9", "", ""),
+        face(Key::Del, "DEL", "INS", ""),
+        face(Key::Ac, "AC", "", ""),
+    ],
+    &[
+        face(Key::_4, "4", "", ""),
+        face(Key::_5, "5", "", ""),
+        face(Key::_6, "6", "", ""),
+        face(Key::Multiply, "×", "nPr", ""),
+        face(Key::Divide, "÷", "nCr", ""),
+    ],
+    &[
+        face(Key::_1, "1", "", ""),
+        face(Key::_2, "2", "S-VAR", ""),
+        face(Key::_3, "3", "", ""),
+        face(Key::Add, "+", "Pol", ""),
+        face(Key::Subtract, "–", "Rec", ""),
+    ],
+    &[
+        face(Key::_0, "0", "", ""),
+        face(Key::Dot, ".", "Ran#", "RanInt"),
+        face(Key::Exp, "EXP", "", ""),
+        face(Key::Ans, "Ans", "", ""),
+        face(Key::Exe, "EXE", "", ""),
+    ],
+];
+
+/// What to show as lit on the keypad.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lit {
+    /// The key pressed last, shown briefly.
+    pub pressed: Option<Key>,
+    pub shift: bool,
+    pub alpha: bool,
+    pub hyp: bool,
+    pub rcl: bool,
+}
+
+const fn cell_width(row: &[Face]) -> usize {
+    WIDTH / row.len()
+}
+
+/// `s` centred in `width` columns.
+fn centre(s: &str, width: usize) -> String {
+    let len = s.chars().count();
+    let left = width.saturating_sub(len) / 2;
+    let right = width.saturating_sub(len + left);
+    format!("{}{s}{}", " ".repeat(left), " ".repeat(right))
+}
+
+/// Draws the keypad with its top left corner at `row`.
+pub fn draw(out: &mut impl Write, row: u16, lit: Lit) -> io::Result<()> {
+    for (i, keys) in ROWS.iter().enumerate() {
+        let width = cell_width(keys);
+        let y = row + i as u16 * ROW_HEIGHT;
+
+        queue!(out, cursor::MoveTo(0, y))?;
+        for f in *keys {
+            let gap = width.saturating_sub(f.shift.chars().count() + f.alpha.chars().count() + 1);
+            queue!(
+                out,
+                style::Print(" ".repeat(gap / 2)),
+                style::PrintStyledContent(f.shift.with(Color::Yellow)),
+                style::Print(" "),
+                style::PrintStyledContent(f.alpha.with(Color::Red)),
+                style::Print(" ".repeat(gap - gap / 2)),
+            )?;
+        }
+
+        queue!(out, cursor::MoveTo(0, y + 1))?;
+        for f in *keys {
+            let on = lit.pressed == Some(f.key)
+                || (lit.shift && f.key == Key::Shift)
+                || (lit.alpha && f.key == Key::Alpha)
+                || (lit.hyp && f.key == Key::Hyp)
+                || (lit.rcl && f.key == Key::Rcl);
+            let label = centre(&format!("[{}]", f.label), width);
+            queue!(out, style::PrintStyledContent(if on { label.reverse() } else { label.stylize() }))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The key at a position on the screen, for a keypad drawn at `row`. The
+/// last key of a row also takes the columns left over by the others.
+pub fn key_at(column: u16, line: u16, row: u16) -> Option<Key> {
+    let keys = ROWS.get(line.checked_sub(row)?.div_floor(ROW_HEIGHT) as usize)?;
+    let column = column as usize;
+    if column >= WIDTH {
+        return None;
+    }
+    keys.get((column / cell_width(keys)).min(keys.len() - 1)).map(|f| f.key)
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn rows() {
+    // the keypad drawn at line 10, each row a line of legends then the keys
+    assert_eq!(key_at(0, 10, 10), Some(Key::Shift));
+    assert_eq!(key_at(0, 11, 10), Some(Key::Shift));
+    assert_eq!(key_at(54, 11, 10), Some(Key::Mode));
+    assert_eq!(key_at(0, 9, 10), None);
+    assert_eq!(key_at(0, 10 + ROWS.len() as u16 * ROW_HEIGHT, 10), None);
+}
+
+#[test]
+fn legends() {
+    // over SOLVE, on the line above FMLA
+    assert_eq!(key_at(20, 12, 10), Some(Key::Fmla));
+    assert_eq!(key_at(20, 13, 10), Some(Key::Fmla));
+}
+
+#[test]
+fn short_rows() {
+    // four keys 13 columns wide, the last also has the 3 left over
+    assert_eq!(key_at(51, 13, 10), Some(Key::Cubed));
+    assert_eq!(key_at(WIDTH as u16 - 1, 13, 10), Some(Key::Cubed));
+    assert_eq!(key_at(WIDTH as u16, 13, 10), None);
+}
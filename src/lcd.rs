@@ -0,0 +1,521 @@
+//! Drawing the display as the dot-matrix LCD of the calculator, in braille
+//! or half-block characters.
+//!
+//! The indicators are in two rows of small letters above the lines, the
+//! modifiers and the mode, then the settings. The top line holds twelve
+//! characters of a 5×7 dot font, the input scrolling within it. Lines too
+//! long for it, as menus are, and the bottom line take the columns of the
+//! text display in a smaller font and seven-segment digits, so that menus
+//! line up with their numbers.
+
+use crate::calculator::{CursorShape, Display};
+
+#[cfg(test)]
+mod tests;
+
+/// Characters of the top line.
+const COLUMNS: usize = 12;
+/// Columns of the input, between the scroll indicators.
+pub const INPUT_COLUMNS: usize = COLUMNS - 2;
+/// Dots taken by each character, with the gap after it.
+const CELL: usize = 6;
+const WIDTH: usize = COLUMNS * CELL;
+/// Dots taken by each small character or digit, with the gap after it.
+const SMALL: usize = 4;
+/// Dots taken by each indicator letter, with the gap after it.
+const INDICATOR: usize = 5;
+/// The two rows of indicators, the top line with the cursor under it, a
+/// gap, then the bottom line.
+const HEIGHT: usize = 28;
+const TOP: usize = 12;
+const BOTTOM: usize = 21;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dots {
+    /// 2×4 dots in each character.
+    Braille,
+    /// 1×2 dots in each character.
+    HalfBlock,
+}
+
+impl Dots {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "braille" => Some(Self::Braille),
+            "blocks" => Some(Self::HalfBlock),
+            _ => None,
+        }
+    }
+
+    /// Terminal lines taken by the LCD.
+    pub const fn lines(self) -> usize {
+        match self {
+            Self::Braille => HEIGHT.div_ceil(4),
+            Self::HalfBlock => HEIGHT.div_ceil(2),
+        }
+    }
+}
+
+struct Canvas {
+    dots: Vec<bool>,
+}
+
+impl Canvas {
+    fn new() -> Self {
+        Self { dots: vec![false; WIDTH * HEIGHT] }
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        if x < WIDTH && y < HEIGHT {
+            self.dots[y * WIDTH + x] = true;
+        }
+    }
+
+    fn flip(&mut self, x: usize, y: usize) {
+        if x < WIDTH && y < HEIGHT {
+            self.dots[y * WIDTH + x] ^= true;
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        x < WIDTH && y < HEIGHT && self.dots[y * WIDTH + x]
+    }
+
+    /// Draws rows of `width` dots, the leftmost dot in the highest bit.
+    fn blit(&mut self, x: usize, y: usize, width: usize, rows: &[u8]) {
+        for (dy, row) in rows.iter().enumerate() {
+            for dx in 0..width {
+                if row >> (width - 1 - dx) & 1 == 1 {
+                    self.set(x + dx, y + dy);
+                }
+            }
+        }
+    }
+
+    fn to_lines(&self, dots: Dots) -> Vec<String> {
+        let (w, h) = match dots {
+            Dots::Braille => (2, 4),
+            Dots::HalfBlock => (1, 2),
+        };
+
+        (0..HEIGHT.div_ceil(h)).map(|row| {
+            (0..WIDTH.div_ceil(w)).map(|col| {
+                let (x, y) = (col * w, row * h);
+                match dots {
+                    Dots::Braille => {
+                        // dot numbering of the braille patterns block
+                        const BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+                        let mut bits = 0;
+                        for (dx, column) in BITS.iter().enumerate() {
+                            for (dy, bit) in column.iter().enumerate() {
+                                if self.get(x + dx, y + dy) {
+                                    bits |= bit;
+                                }
+                            }
+                        }
+                        char::from_u32(0x2800 + bits).unwrap_or(' ')
+                    },
+                    Dots::HalfBlock => match (self.get(x, y), self.get(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                }
+            }).collect()
+        }).collect()
+    }
+}
+
+/// The display as lines of the LCD.
+pub fn render(display: &Display, dots: Dots) -> Vec<String> {
+    let Display { stat, top, bot, cursor } = display;
+    let mut canvas = Canvas::new();
+
+    // each indicator keeps its place, the settings wrapping to the second
+    // row
+    let per_row = WIDTH / INDICATOR;
+    for (i, c) in stat.chars().take(2 * per_row).enumerate() {
+        if c != ' ' {
+            canvas.blit(i % per_row * INDICATOR, i / per_row * 6, 3, &small(c));
+        }
+    }
+
+    let text: String = top.iter().map(|b| b.text()).collect();
+    let columns = text.trim_end().chars().filter(|c| !is_combining(*c)).count();
+    let pitch = if columns <= COLUMNS { CELL } else { SMALL };
+    let mut x = None;
+    for c in text.chars() {
+        match (is_combining(c), x) {
+            (true, Some(x)) if pitch == CELL => canvas.blit(x, TOP, 5, &[0x04, 0x0a]),
+            (true, Some(x)) => canvas.blit(x, TOP, 3, &[2, 5]),
+            (true, None) => (),
+            (false, _) => {
+                let at = x.map_or(0, |x| x + pitch);
+                if pitch == CELL {
+                    canvas.blit(at, TOP, 5, &glyph(c));
+                } else {
+                    canvas.blit(at, TOP + 1, 3, &small(c));
+                }
+                x = Some(at);
+            },
+        }
+    }
+
+    // the first column is for the left scroll indicator
+    if let Some((at, shape)) = *cursor {
+        let x = (at + 1) * pitch;
+        match shape {
+            CursorShape::Bar => for y in TOP..TOP + 7 {
+                canvas.set(x - 1, y);
+            },
+            CursorShape::Underline => for dx in 0..pitch - 1 {
+                canvas.set(x + dx, TOP + 7);
+            },
+            CursorShape::Block => for dx in 0..pitch - 1 {
+                for y in TOP..TOP + 7 {
+                    canvas.flip(x + dx, y);
+                }
+            },
+        }
+    }
+
+    for (i, c) in bot.chars().take(WIDTH / SMALL).enumerate() {
+        let x = i * SMALL;
+        match c {
+            '0'..='9' | '-' | '–' => segments(&mut canvas, x, c),
+            '.' => canvas.set(x + 1, BOTTOM + 6),
+            c => match superscript(c) {
+                Some(c) => canvas.blit(x, BOTTOM, 3, &small(c)),
+                None => canvas.blit(x, BOTTOM + 2, 3, &small(c)),
+            },
+        }
+    }
+
+    canvas.to_lines(dots)
+}
+
+/// Combining marks, drawn over the character before them.
+const fn is_combining(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036f}')
+}
+
+/// A digit or minus sign of the bottom line, three dots wide.
+fn segments(canvas: &mut Canvas, x: usize, c: char) {
+    // a b c d e f g, clockwise from the top and the middle last
+    let on: u8 = match c {
+        '0' => 0b111_1110,
+        '1' => 0b011_0000,
+        '2' => 0b110_1101,
+        '3' => 0b111_1001,
+        '4' => 0b011_0011,
+        '5' => 0b101_1011,
+        '6' => 0b101_1111,
+        '7' => 0b111_0000,
+        '8' => 0b111_1111,
+        '9' => 0b111_1011,
+        _ => 0b000_0001,
+    };
+    let y = BOTTOM;
+    let lines: [(usize, usize, usize, usize); 7] = [
+        (0, 0, 3, 1), // a
+        (2, 0, 1, 4), // b
+        (2, 3, 1, 4), // c
+        (0, 6, 3, 1), // d
+        (0, 3, 1, 4), // e
+        (0, 0, 1, 4), // f
+        (0, 3, 3, 1), // g
+    ];
+
+    for (i, (sx, sy, w, h)) in lines.into_iter().enumerate() {
+        if on >> (6 - i) & 1 == 1 {
+            for dx in 0..w {
+                for dy in 0..h {
+                    canvas.set(x + sx + dx, y + sy + dy);
+                }
+            }
+        }
+    }
+}
+
+/// The character an exponent digit is a small one of.
+const fn superscript(c: char) -> Option<char> {
+    Some(match c {
+        '⁰' => '0',
+        '¹' => '1',
+        '²' => '2',
+        '³' => '3',
+        '⁴' => '4',
+        '⁵' => '5',
+        '⁶' => '6',
+        '⁷' => '7',
+        '⁸' => '8',
+        '⁹' => '9',
+        '⁻' => '-',
+        _ => return None,
+    })
+}
+
+/// A character of the small font, three dots wide, for the indicators,
+/// the bottom line and long top lines. A block for those it does not have.
+const fn small(c: char) -> [u8; 5] {
+    match c {
+        ' ' => [0, 0, 0, 0, 0],
+        '!' => [2, 2, 2, 0, 2],
+        '#' => [5, 7, 5, 7, 5],
+        '\'' | '’' => [2, 2, 4, 0, 0],
+        '”' => [5, 5, 0, 0, 0],
+        '(' => [1, 2, 2, 2, 1],
+        ')' => [4, 2, 2, 2, 4],
+        '*' => [5, 2, 7, 2, 5],
+        '+' => [0, 2, 7, 2, 0],
+        ',' => [0, 0, 0, 2, 4],
+        '-' | '–' | '╶' => [0, 0, 7, 0, 0],
+        '.' => [0, 0, 0, 0, 2],
+        '/' => [1, 1, 2, 4, 4],
+        '0' => [7, 5, 5, 5, 7],
+        '1' => [2, 6, 2, 2, 7],
+        '2' => [7, 1, 7, 4, 7],
+        '3' => [7, 1, 7, 1, 7],
+        '4' => [5, 5, 7, 1, 1],
+        '5' => [7, 4, 7, 1, 7],
+        '6' => [7, 4, 7, 5, 7],
+        '7' => [7, 1, 1, 1, 1],
+        '8' => [7, 5, 7, 5, 7],
+        '9' => [7, 5, 7, 1, 7],
+        ':' => [0, 2, 0, 2, 0],
+        '<' => [1, 2, 4, 2, 1],
+        '=' => [0, 7, 0, 7, 0],
+        '>' => [4, 2, 1, 2, 4],
+        '?' => [6, 1, 2, 0, 2],
+        'A' => [2, 5, 7, 5, 5],
+        'B' => [6, 5, 6, 5, 6],
+        'C' => [3, 4, 4, 4, 3],
+        'D' => [6, 5, 5, 5, 6],
+        'E' => [7, 4, 6, 4, 7],
+        'F' => [7, 4, 6, 4, 4],
+        'G' => [3, 4, 5, 5, 3],
+        'H' => [5, 5, 7, 5, 5],
+        'I' => [7, 2, 2, 2, 7],
+        'J' => [1, 1, 1, 5, 2],
+        'K' => [5, 5, 6, 5, 5],
+        'L' => [4, 4, 4, 4, 7],
+        'M' => [5, 7, 7, 5, 5],
+        'N' => [6, 5, 5, 5, 5],
+        'O' => [2, 5, 5, 5, 2],
+        'P' => [6, 5, 6, 4, 4],
+        'Q' => [2, 5, 5, 6, 3],
+        'R' => [6, 5, 6, 5, 5],
+        'S' => [3, 4, 2, 1, 6],
+        'T' => [7, 2, 2, 2, 2],
+        'U' => [5, 5, 5, 5, 7],
+        'V' => [5, 5, 5, 5, 2],
+        'W' => [5, 5, 7, 7, 5],
+        'X' => [5, 5, 2, 5, 5],
+        'Y' => [5, 5, 2, 2, 2],
+        'Z' => [7, 1, 2, 4, 7],
+        '[' => [6, 4, 4, 4, 6],
+        ']' => [3, 1, 1, 1, 3],
+        '^' => [2, 5, 0, 0, 0],
+        '_' => [0, 0, 0, 0, 7],
+        'a' => [0, 3, 5, 5, 3],
+        'b' => [4, 4, 6, 5, 6],
+        'c' => [0, 3, 4, 4, 3],
+        'd' => [1, 1, 3, 5, 3],
+        'e' => [0, 2, 5, 6, 3],
+        'f' => [1, 2, 7, 2, 2],
+        'g' => [0, 3, 5, 3, 6],
+        'h' => [4, 4, 6, 5, 5],
+        'i' => [2, 0, 2, 2, 2],
+        'j' => [1, 0, 1, 5, 2],
+        'k' => [4, 5, 6, 6, 5],
+        'l' => [6, 2, 2, 2, 7],
+        'm' => [0, 5, 7, 5, 5],
+        'n' => [0, 6, 5, 5, 5],
+        'o' => [0, 2, 5, 5, 2],
+        'p' => [0, 6, 5, 6, 4],
+        'q' => [0, 3, 5, 3, 1],
+        'r' => [0, 5, 6, 4, 4],
+        's' => [0, 3, 6, 1, 6],
+        't' => [2, 7, 2, 2, 1],
+        'u' => [0, 5, 5, 5, 3],
+        'v' => [0, 5, 5, 5, 2],
+        'w' => [0, 5, 5, 7, 5],
+        'x' => [0, 5, 2, 2, 5],
+        'y' => [0, 5, 3, 1, 6],
+        'z' => [0, 7, 1, 2, 7],
+        '~' => [0, 3, 6, 0, 0],
+        '×' => [0, 5, 2, 5, 0],
+        '÷' => [2, 0, 7, 0, 2],
+        '√' => [3, 2, 2, 6, 2],
+        '⅃' => [1, 1, 1, 1, 7],
+        'ᴇ' => [0, 7, 6, 4, 7],
+        '⒑' => [0, 7, 7, 7, 0],
+        '→' => [2, 1, 7, 1, 2],
+        '←' => [2, 4, 7, 4, 2],
+        '▶' => [4, 6, 7, 6, 4],
+        '◀' => [1, 3, 7, 3, 1],
+        '◢' => [0, 1, 3, 7, 0],
+        '⇒' => [2, 7, 1, 7, 2],
+        '≠' => [1, 7, 2, 7, 4],
+        '≥' => [4, 2, 4, 0, 7],
+        '≤' => [1, 2, 1, 0, 7],
+        '∫' => [1, 2, 2, 2, 4],
+        'Σ' => [7, 4, 2, 4, 7],
+        'Π' => [7, 5, 5, 5, 5],
+        '°' => [2, 5, 2, 0, 0],
+        '²' => [6, 2, 4, 6, 0],
+        '∠' => [0, 1, 2, 4, 7],
+        'θ' => [2, 5, 7, 5, 2],
+        'μ' => [0, 5, 5, 7, 4],
+        'α' => [0, 3, 6, 6, 3],
+        'ħ' => [4, 7, 4, 6, 5],
+        'λ' => [4, 2, 2, 5, 5],
+        'γ' => [0, 5, 5, 2, 2],
+        'σ' => [0, 3, 6, 5, 2],
+        'ε' => [0, 3, 6, 4, 3],
+        'φ' => [2, 7, 5, 7, 2],
+        '∞' => [0, 0, 7, 7, 0],
+        'ᴀ' => [0, 2, 5, 7, 5],
+        'ŷ' => [2, 5, 3, 1, 6],
+        '₀' => [0, 0, 7, 5, 7],
+        '₁' => [0, 0, 6, 2, 7],
+        '₂' => [0, 0, 6, 2, 3],
+        'ₑ' => [0, 0, 7, 6, 7],
+        'ₙ' => [0, 0, 6, 5, 5],
+        'ₚ' => [0, 0, 6, 7, 4],
+        _ => [7, 7, 7, 7, 7],
+    }
+}
+
+/// A character of the 5×7 font, a box for those it does not have.
+const fn glyph(c: char) -> [u8; 7] {
+    match c {
+        ' ' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x00, 0x00, 0x04],
+        '#' => [0x0a, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x0a],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '*' => [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00],
+        '+' => [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08],
+        '-' | '–' => [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00],
+        '╶' => [0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '0' => [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e],
+        '1' => [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e],
+        '2' => [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f],
+        '3' => [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e],
+        '4' => [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02],
+        '5' => [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e],
+        '6' => [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e],
+        '7' => [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e],
+        '9' => [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c],
+        ':' => [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00],
+        ';' => [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x04, 0x08],
+        '<' => [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02],
+        '=' => [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00],
+        '>' => [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08],
+        '?' => [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+        'A' => [0x0e, 0x11, 0x11, 0x11, 0x1f, 0x11, 0x11],
+        'B' => [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e],
+        'C' => [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e],
+        'D' => [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c],
+        'E' => [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f],
+        'F' => [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10],
+        'G' => [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f],
+        'H' => [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11],
+        'I' => [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f],
+        'M' => [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e],
+        'P' => [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10],
+        'Q' => [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d],
+        'R' => [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11],
+        'S' => [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e],
+        'T' => [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a],
+        'X' => [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0a, 0x04, 0x04, 0x04],
+        'Z' => [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f],
+        '[' => [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e],
+        ']' => [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e],
+        '^' => [0x04, 0x0a, 0x11, 0x00, 0x00, 0x00, 0x00],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f],
+        'a' => [0x00, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f],
+        'b' => [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1e],
+        'c' => [0x00, 0x00, 0x0e, 0x10, 0x10, 0x11, 0x0e],
+        'd' => [0x01, 0x01, 0x0d, 0x13, 0x11, 0x11, 0x0f],
+        'e' => [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e],
+        'f' => [0x06, 0x09, 0x08, 0x1c, 0x08, 0x08, 0x08],
+        'g' => [0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x0e],
+        'h' => [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11],
+        'i' => [0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e],
+        'j' => [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0c],
+        'k' => [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12],
+        'l' => [0x0c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e],
+        'm' => [0x00, 0x00, 0x1a, 0x15, 0x15, 0x11, 0x11],
+        'n' => [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11],
+        'o' => [0x00, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e],
+        'p' => [0x00, 0x00, 0x1e, 0x11, 0x1e, 0x10, 0x10],
+        'q' => [0x00, 0x00, 0x0d, 0x13, 0x0f, 0x01, 0x01],
+        'r' => [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10],
+        's' => [0x00, 0x00, 0x0e, 0x10, 0x0e, 0x01, 0x1e],
+        't' => [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06],
+        'u' => [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d],
+        'v' => [0x00, 0x00, 0x11, 0x11, 0x11, 0x0a, 0x04],
+        'w' => [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0a],
+        'x' => [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11],
+        'y' => [0x00, 0x00, 0x11, 0x11, 0x0f, 0x01, 0x0e],
+        'z' => [0x00, 0x00, 0x1f, 0x02, 0x04, 0x08, 0x1f],
+        '×' => [0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00],
+        '÷' => [0x00, 0x04, 0x00, 0x1f, 0x00, 0x04, 0x00],
+        '√' => [0x03, 0x02, 0x02, 0x02, 0x12, 0x0a, 0x04],
+        '⅃' => [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x1f],
+        'ᴇ' => [0x00, 0x0f, 0x08, 0x0e, 0x08, 0x0f, 0x00],
+        '⒑' => [0x00, 0x17, 0x15, 0x15, 0x15, 0x17, 0x00],
+        '→' => [0x00, 0x04, 0x02, 0x1f, 0x02, 0x04, 0x00],
+        '←' => [0x00, 0x04, 0x08, 0x1f, 0x08, 0x04, 0x00],
+        '▶' => [0x08, 0x0c, 0x0e, 0x0f, 0x0e, 0x0c, 0x08],
+        '◀' => [0x02, 0x06, 0x0e, 0x1e, 0x0e, 0x06, 0x02],
+        '◢' => [0x00, 0x01, 0x03, 0x07, 0x0f, 0x1f, 0x00],
+        '⇒' => [0x00, 0x04, 0x1e, 0x01, 0x1e, 0x04, 0x00],
+        '≠' => [0x00, 0x01, 0x1f, 0x04, 0x1f, 0x10, 0x00],
+        '≥' => [0x08, 0x04, 0x02, 0x04, 0x08, 0x00, 0x1f],
+        '≤' => [0x02, 0x04, 0x08, 0x04, 0x02, 0x00, 0x1f],
+        '∫' => [0x02, 0x05, 0x04, 0x04, 0x04, 0x14, 0x08],
+        'Σ' => [0x1f, 0x10, 0x08, 0x04, 0x08, 0x10, 0x1f],
+        'Π' => [0x1f, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a],
+        '°' => [0x0c, 0x12, 0x12, 0x0c, 0x00, 0x00, 0x00],
+        '’' => [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+        '”' => [0x0a, 0x0a, 0x14, 0x00, 0x00, 0x00, 0x00],
+        '∠' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x1f, 0x00],
+        'θ' => [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x0e],
+        'μ' => [0x00, 0x00, 0x11, 0x11, 0x13, 0x1d, 0x10],
+        'α' => [0x00, 0x00, 0x09, 0x15, 0x12, 0x12, 0x0d],
+        'ħ' => [0x10, 0x1c, 0x16, 0x19, 0x11, 0x11, 0x11],
+        'λ' => [0x18, 0x04, 0x04, 0x0a, 0x0a, 0x11, 0x11],
+        'γ' => [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x04],
+        'σ' => [0x00, 0x00, 0x0f, 0x12, 0x11, 0x11, 0x0e],
+        'ε' => [0x00, 0x00, 0x0f, 0x10, 0x0e, 0x10, 0x0f],
+        'φ' => [0x04, 0x04, 0x0e, 0x15, 0x15, 0x0e, 0x04],
+        '∞' => [0x00, 0x00, 0x0a, 0x15, 0x15, 0x0a, 0x00],
+        'ᴀ' => [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x11, 0x11],
+        'ŷ' => [0x04, 0x0a, 0x11, 0x11, 0x0f, 0x01, 0x0e],
+        '²' => [0x0c, 0x02, 0x04, 0x08, 0x0e, 0x00, 0x00],
+        '₀' => [0x00, 0x00, 0x00, 0x06, 0x09, 0x09, 0x06],
+        '₁' => [0x00, 0x00, 0x00, 0x02, 0x06, 0x02, 0x07],
+        '₂' => [0x00, 0x00, 0x00, 0x06, 0x01, 0x02, 0x07],
+        'ₑ' => [0x00, 0x00, 0x00, 0x06, 0x0f, 0x08, 0x06],
+        'ₙ' => [0x00, 0x00, 0x00, 0x0e, 0x09, 0x09, 0x09],
+        'ₚ' => [0x00, 0x00, 0x00, 0x0e, 0x09, 0x0e, 0x08],
+        _ => [0x1f, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1f],
+    }
+}
@@ -0,0 +1,109 @@
+use fx_maths::{constants::CONSTANTS, conversion::CONVERSIONS, formula::FORMULAS};
+
+use super::*;
+use crate::{Calculator, parse_script};
+
+fn display(stat: &str, bot: &str, cursor: Option<(usize, CursorShape)>) -> Display {
+    Display { stat: stat.to_string(), top: Vec::new(), bot: bot.to_string(), cursor }
+}
+
+/// Column `col` of each of the lines, from `from` on.
+fn column(lines: &[String], col: usize, from: usize) -> String {
+    lines[from..].iter().map(|l| l.chars().nth(col).unwrap()).collect()
+}
+
+#[test]
+fn digit() {
+    // 1 lights segments b and c, the third column of its cell
+    let lines = render(&display("", "1", None), Dots::HalfBlock);
+    assert_eq!(lines.len(), Dots::HalfBlock.lines());
+    assert!(lines.iter().all(|l| l.chars().count() == WIDTH));
+    assert_eq!(column(&lines, 2, BOTTOM / 2), "▄███");
+    assert!(lines.iter().all(|l| l.chars().enumerate().all(|(i, c)| i == 2 || c == ' ')));
+
+    let lines = render(&display("", "1", None), Dots::Braille);
+    assert_eq!(lines.len(), Dots::Braille.lines());
+    assert!(lines.iter().all(|l| l.chars().count() == WIDTH / 2));
+    assert_eq!(column(&lines, 1, BOTTOM / 4), "⡆⡇");
+    assert_eq!(column(&lines, 0, 0), "⠀".repeat(Dots::Braille.lines()));
+}
+
+#[test]
+fn cursor() {
+    // the first cell is left for the scroll indicator
+    let bar = render(&display("", "", Some((0, CursorShape::Bar))), Dots::HalfBlock);
+    assert_eq!(column(&bar, CELL - 1, TOP / 2), "███▀    ");
+
+    let underline = render(&display("", "", Some((0, CursorShape::Underline))), Dots::HalfBlock);
+    assert_eq!(underline[TOP / 2 + 3].chars().skip(CELL).take(6).collect::<String>(), "▄▄▄▄▄ ");
+    assert_eq!(column(&underline, CELL, TOP / 2), "   ▄    ");
+
+    let block = render(&display("", "", Some((0, CursorShape::Block))), Dots::HalfBlock);
+    assert_eq!(column(&block, CELL + 4, TOP / 2), "███▀    ");
+}
+
+#[test]
+fn indicators() {
+    let lines = render(&display("S     ", "", None), Dots::HalfBlock);
+    assert_eq!(lines[..3].iter().map(|l| l.trim_end()).collect::<Vec<_>>(), ["▄▀▀", " ▀▄", "▀▀"]);
+    assert!(lines[3..].iter().all(|l| l.trim().is_empty()));
+
+    // FIX and D in their places on the second row, the ones not lit are
+    // left out
+    let lines = render(&display("              FIX D", "", None), Dots::HalfBlock);
+    assert!(lines[0].trim().is_empty());
+    let lit: Vec<_> = lines[3].chars().enumerate().filter(|(_, c)| *c != ' ').map(|(i, _)| i).collect();
+    assert_eq!(lit.first(), Some(&0));
+    assert_eq!(lit.last(), Some(&(4 * INDICATOR + 2)));
+}
+
+fn calculator(keys: &str) -> Display {
+    let mut c = Calculator::new();
+    c.set_input_columns(INPUT_COLUMNS);
+    c.run_keys(&parse_script(keys).unwrap());
+    c.get_display()
+}
+
+#[test]
+fn top_line() {
+    // the input scrolls within the twelve columns, the cursor after the
+    // ninth one
+    let d = calculator("1 1 1 1 1 1 1 1 1 1 1 1 1 1");
+    let text: String = d.top.iter().map(|b| b.text()).collect();
+    assert_eq!(text, "◀111111111  ");
+    let lines = render(&d, Dots::HalfBlock);
+    assert_eq!(column(&lines, 10 * CELL - 1, TOP / 2), "███▀    ");
+
+    // the hat of x̂ goes over the x
+    let lines = render(&calculator("Mode 5 1 Shift 2 Right 2"), Dots::HalfBlock);
+    assert_eq!(column(&lines, CELL + 2, TOP / 2), "▀ ▀     ");
+}
+
+#[test]
+fn long_lines() {
+    // a menu takes the columns of the text display, its numbers under the
+    // items
+    let d = calculator("Mode");
+    let number = d.bot.chars().position(|c| c == '1').unwrap();
+    let lines = render(&d, Dots::HalfBlock);
+    // the O of COMP over the 1
+    assert_eq!(column(&lines, number * SMALL + 2, TOP / 2), " █▀ ▄███");
+}
+
+#[test]
+fn glyphs() {
+    let missing = glyph('\u{fffd}');
+    let tokens = "^(√(e⅃0123456789+–×÷╶⒑^(.ᴇ()Ans,ABCrx̂₁x̂₂ŷ:◢DEFMXY→?Lbl Goto ⇒Isz Dsz =≠>≥<≤∫(d/dx(Σ(Π(!PRan#RanInt#(Pol(Rec(▶";
+    let symbols = CONSTANTS.iter().map(|c| c.symbol).chain(CONVERSIONS.iter().flat_map(|c| [c.from, c.to]));
+    for c in symbols.clone().chain([tokens]).flat_map(str::chars).filter(|c| !is_combining(*c)) {
+        assert_ne!(glyph(c), missing, "{c}");
+    }
+
+    // and the small font has what the menus show
+    let missing = small('\u{fffd}');
+    let menus = "←→ COMP CMPLX BASE SD REG PRGM Lin Log Exp Pwr Inv Quad AB-Exp r ŷ x̂₁ x̂₂ ?→:◢⇒ Lbl Goto Isz Dsz =≠>≥<≤ NEW Edt Del Deg Rad Gra Fix Sci Norm 0~9 ab/c d/c a+bi r∠θ FreqOn FreqOff";
+    let names = FORMULAS.iter().map(|f| f.name);
+    for c in symbols.chain(names).chain([menus]).flat_map(str::chars).filter(|c| !is_combining(*c)) {
+        assert_ne!(small(c), missing, "{c}");
+    }
+}
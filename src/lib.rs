@@ -0,0 +1,26 @@
+//! The Casio fx-50FH II calculator without a terminal. Keys go in with
+//! `Calculator::press` and `Calculator::tick`, `Calculator::get_display`
+//! gives what the display shows and `lcd` draws it as the LCD.
+
+#![feature(int_roundings)]
+#![warn(
+    clippy::complexity,
+    clippy::correctness,
+    clippy::perf,
+    clippy::nursery,
+    clippy::suspicious,
+    clippy::style,
+)]
+#![allow(
+    clippy::semicolon_inside_block,
+    clippy::just_underscores_and_digits,
+)]
+
+mod calculator;
+pub mod lcd;
+
+pub use calculator::{
+    Base, Calculator, CursorShape, Display, DisplayBlock, DisplayBlocks, Error, KEY_NAMES, Key,
+    LoadError, Mode, UnknownKey, Var, parse_script,
+};
+pub use fx_maths::{Float, stat::Regression};
@@ -12,13 +12,27 @@
     clippy::just_underscores_and_digits,
 )]
 
-use std::{io::{Write, stdout}, time::*};
-use crossterm::{*, style::{Color, Stylize}, event::*};
+use std::{io::{Write, stdin, stdout}, time::*};
+use crossterm::{*, style::{Color, StyledContent, Stylize}, event::*};
 
-mod calculator;
-use calculator::Calculator;
+mod keymap;
+mod keypad;
+use fx_50fh_ii::{Calculator, CursorShape, DisplayBlock, DisplayBlocks, lcd::{self, Dots}};
+use keymap::{Keymap, Layout};
+
+/// First line of the display, under the title and the status line.
+const DISPLAY_ROW: u16 = 3;
+/// How long a pressed key stays lit on the keypad.
+const FLASH: Duration = Duration::from_millis(150);
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = parse_args()?;
+    if let Some(path) = &args.script {
+        return run_script(path);
+    }
+
+    let (keymap, problems) = Keymap::load(args.layout);
+
     let mut stdout = stdout();
     execute!(
         stdout,
@@ -30,6 +44,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         cursor::MoveTo(0, 0)
     )?;
     terminal::enable_raw_mode()?;
+    if args.keypad {
+        execute!(stdout, event::EnableMouseCapture)?;
+    }
 
     queue!(stdout, style::PrintStyledContent(
         "Virtual fx-50FH II    BSD-2-Clause-Patent"
@@ -39,67 +56,83 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     stdout.flush()?;
 
-    let mut calc = Calculator::new();
+    // the keypad goes under the display, leaving a line free
+    let keypad_row = DISPLAY_ROW + args.lcd.map_or(2, Dots::lines) as u16 + 1;
+
+    let mut calc = Calculator::restore();
+    if args.lcd.is_some() {
+        calc.set_input_columns(lcd::INPUT_COLUMNS);
+    }
+    let mut flash = None;
 
     'main_loop: loop {
         if poll(Duration::from_millis(100))? {
             match read()? {
                 Event::Key(KeyEvent { code: KeyCode::Esc, kind: KeyEventKind::Press, .. }) => break 'main_loop,
-                Event::Key(ke) => calc.pretick(Some(ke)),
+                Event::Key(ke) => calc.press_keys(keymap.get(&ke)),
+                Event::Mouse(MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column, row, .. }) if args.keypad => {
+                    if let Some(k) = keypad::key_at(column, row, keypad_row) {
+                        calc.press(k);
+                    }
+                },
                 _ => ()
             }
-        } else {
-            calc.pretick(None)
         }
 
         calc.tick();
+        if let Some(k) = calc.pressed_key() {
+            flash = Some((k, Instant::now()));
+        }
 
-        let (stat, top, bot, cursor) = calc.get_display();
+        let display = calc.get_display();
         queue!(
             stdout,
 
             cursor::MoveTo(0, 2),
             terminal::Clear(terminal::ClearType::FromCursorDown),
 
-            style::Print(stat),
-
-            cursor::MoveTo(0, 3)
+            // the LCD draws its own indicators
+            style::Print(if args.lcd.is_some() { "" } else { &display.stat }),
         )?;
 
-        for i in top {
-            queue!(
-                stdout,
+        if let Some(dots) = args.lcd {
+            for (i, line) in lcd::render(&display, dots).into_iter().enumerate() {
+                queue!(
+                    stdout,
 
-                style::PrintStyledContent(
-                    i.as_styled().with(Color::White)
-                )
-            )?;
+                    cursor::MoveTo(0, DISPLAY_ROW + i as u16),
+                    style::PrintStyledContent(
+                        line.with(Color::Black)
+                            .on(Color::Rgb { r: 0xb4, g: 0xc4, b: 0xa4 })
+                    ),
+                )?;
+            }
+        } else {
+            draw_text(&mut stdout, &display.top, &display.bot)?;
         }
 
-        queue!(
-            stdout,
-
-            cursor::MoveTo(0, 4),
-            style::PrintStyledContent(
-                bot .bold()
-                    .with(Color::Blue)
-            ),
-        )?;
-
-        match cursor {
-            Some((x, false)) => queue!(
-                stdout,
+        if args.keypad {
+            let lit = keypad::Lit {
+                pressed: flash.filter(|(_, t): &(_, Instant)| t.elapsed() < FLASH).map(|(k, _)| k),
+                shift: calc.shift_active(),
+                alpha: calc.alpha_active(),
+                hyp: calc.hyp_active(),
+                rcl: calc.rcl_active(),
+            };
+            keypad::draw(&mut stdout, keypad_row, lit)?;
+        }
 
-                cursor::Show,
-                cursor::MoveTo(x as u16 + 1, 3),
-                cursor::SetCursorStyle::BlinkingBar,
-            )?,
-            Some((x, true)) => queue!(
+        match display.cursor.filter(|_| args.lcd.is_none()) {
+            Some((x, shape)) => queue!(
                 stdout,
 
                 cursor::Show,
-                cursor::MoveTo(x as u16 + 1, 3),
-                cursor::SetCursorStyle::BlinkingUnderScore,
+                cursor::MoveTo(x as u16 + 1, DISPLAY_ROW),
+                match shape {
+                    CursorShape::Bar => cursor::SetCursorStyle::BlinkingBar,
+                    CursorShape::Underline => cursor::SetCursorStyle::BlinkingUnderScore,
+                    CursorShape::Block => cursor::SetCursorStyle::BlinkingBlock,
+                },
             )?,
             None => queue!(stdout, cursor::Hide)?,
         }
@@ -107,6 +140,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         stdout.flush()?;
     }
     
+    if args.keypad {
+        execute!(stdout, event::DisableMouseCapture)?;
+    }
     terminal::disable_raw_mode()?;
     execute!(
         stdout,
@@ -115,5 +151,109 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         cursor::Show,
         cursor::SetCursorStyle::DefaultUserShape
     )?;
+
+    // shown after leaving the alternate screen so that they stay visible
+    for p in problems {
+        eprintln!("keymap.toml: {p}");
+    }
+
+    if !calc.persists() {
+        eprintln!("state: written by a newer version, left as it is");
+    }
+
+    calc.persist()?;
+    Ok(())
+}
+
+/// Draws the display as text, the result under the input.
+fn draw_text(stdout: &mut impl Write, top: &DisplayBlocks, bot: &str) -> std::io::Result<()> {
+    queue!(stdout, cursor::MoveTo(0, DISPLAY_ROW))?;
+    for i in top {
+        queue!(
+            stdout,
+
+            style::PrintStyledContent(
+                styled(i).with(Color::White)
+            )
+        )?;
+    }
+
+    queue!(
+        stdout,
+
+        cursor::MoveTo(0, DISPLAY_ROW + 1),
+        style::PrintStyledContent(
+            bot .bold()
+                .with(Color::Blue)
+        ),
+    )
+}
+
+fn styled(block: &DisplayBlock) -> StyledContent<&str> {
+    let mut c = block.text().stylize();
+    if block.bold() { c = c.bold(); }
+    if block.italic() { c = c.italic(); }
+    c
+}
+
+/// Presses the keys of a script on a new calculator and prints the
+/// display, without a terminal.
+fn run_script(path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let text = if path == "-" {
+        std::io::read_to_string(stdin())?
+    } else {
+        std::fs::read_to_string(path)?
+    };
+
+    let mut calc = Calculator::new();
+    calc.run_keys(&fx_50fh_ii::parse_script(&text).map_err(|e| e.to_string())?);
+    println!("{}", calc.display_text());
     Ok(())
 }
+
+#[derive(Debug, Default)]
+struct Args {
+    /// `--layout <name>`
+    layout: Option<Layout>,
+    /// `--keypad`, draws the keys under the display.
+    keypad: bool,
+    /// `--lcd <braille|blocks>`, draws the display as the LCD.
+    lcd: Option<Dots>,
+    /// `run <script>`, runs a script of keys instead, `-` reading it from
+    /// standard input.
+    script: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(a) = args.next() {
+        let (name, inline) = match a.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (a.as_str(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next()).ok_or_else(|| format!("{name} needs a value"));
+
+        match name {
+            "--keypad" if inline.is_none() => parsed.keypad = true,
+            "run" if inline.is_none() => {
+                parsed.script = Some(args.next().ok_or("run needs a script, - for standard input")?);
+            },
+            "--layout" => {
+                let value = value()?;
+                parsed.layout = Some(Layout::from_name(&value).ok_or_else(|| {
+                    format!("unknown layout `{value}`, expected qwerty-grid, natural or numpad")
+                })?);
+            },
+            "--lcd" => {
+                let value = value()?;
+                parsed.lcd = Some(Dots::from_name(&value).ok_or_else(|| {
+                    format!("unknown LCD dots `{value}`, expected braille or blocks")
+                })?);
+            },
+            _ => return Err(format!("unknown argument `{a}`")),
+        }
+    }
+
+    Ok(parsed)
+}
@@ -0,0 +1,379 @@
+use fx_50fh_ii::{Calculator, CursorShape, Error, Float, Key, Mode, Regression, UnknownKey, Var, parse_script};
+
+fn run(script: &str) -> Calculator {
+    let mut calc = Calculator::new();
+    calc.run_keys(&parse_script(script).unwrap());
+    calc
+}
+
+fn float(s: &str) -> Float {
+    s.parse().unwrap()
+}
+
+#[test]
+fn scripts() {
+    assert_eq!(
+        parse_script("SHIFT sin 3 0 EXE # arcsin\n\nac").unwrap(),
+        vec![Key::Shift, Key::Sin, Key::_3, Key::_0, Key::Exe, Key::Ac],
+    );
+    assert_eq!(
+        parse_script("1 Add\n2 Plus"),
+        Err(UnknownKey { line: 2, word: "Plus".to_string() }),
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(run("1 Divide 3 Exe").display_text(), "                  D\n 1÷3\n     0.3333333333");
+    assert_eq!(run("5 Shift PowNegOne Exe").display_text(), "                  D\n 5!\n              120");
+    // Fix 3 from the SETUP menu
+    assert_eq!(
+        run("Shift Mode Right 1 3 1 Divide 3 Exe").display_text(),
+        "              FIX D\n 1÷3\n            0.333",
+    );
+    // r∠θ from the fourth SETUP page, d/c on the third shows nothing
+    assert_eq!(run("Shift Mode Right Right Right 2").display_text(), "                  D r∠θ\n\n");
+    assert_eq!(run("Shift Mode Right Right 2").display_text(), "                  D\n\n");
+}
+
+#[test]
+fn state() {
+    let calc = run("2 Add 3 Exe");
+    assert_eq!(calc.result(), Some(&float("5")));
+    assert_eq!(calc.answer(), &float("5"));
+    assert_eq!(calc.error(), None);
+
+    let calc = run("1 Divide 0 Exe");
+    assert_eq!(calc.error(), Some(Error::Math));
+    assert_eq!(calc.display_text(), "                  D\n Math ERROR\n  [AC]:Cancel");
+
+    // STO A
+    assert_eq!(run("5 Shift Rcl Negative").variable(Var::A), &float("5"));
+
+    assert_eq!(Calculator::new().mode(), Mode::Computation);
+    assert_eq!(run("Mode 2").mode(), Mode::Complex);
+
+    // hyp and RCL stay lit until the next key
+    assert!(run("Hyp").hyp_active() && run("Hyp Shift").hyp_active());
+    assert!(!run("Hyp Hyp").hyp_active());
+    assert!(run("Rcl").rcl_active() && run("Shift Rcl").rcl_active());
+    assert!(!run("Rcl Negative").rcl_active());
+}
+
+#[test]
+fn editing() {
+    let calc = run("1 2 3 Left Left Shift Del 9");
+    let display = calc.get_display();
+    assert_eq!(display.top.iter().map(|b| b.text()).collect::<String>().trim_end(), " 193");
+    assert_eq!(display.cursor, Some((2, CursorShape::Underline)));
+
+    // DEL deletes before the cursor, nothing at the start
+    let text = |calc: Calculator| calc.get_display().top.iter().map(|b| b.text()).collect::<String>().trim_end().to_string();
+    assert_eq!(text(run("1 2 3 Left Del")), " 13");
+    assert_eq!(text(run("1 2 3 Left Left Left Del")), " 123");
+    assert_eq!(text(run("1 2 3 Left Left Left Shift Del Del")), " 23");
+
+    // 99 steps at most, the cursor a block with ten left
+    let calc = run(&"1 ".repeat(120));
+    let display = calc.get_display();
+    assert_eq!(display.top.iter().map(|b| b.text()).collect::<String>(), format!("◀{}  ", "1".repeat(15)));
+    assert_eq!(display.cursor, Some((15, CursorShape::Block)));
+    assert_eq!(run(&format!("{}Exe", "1 ".repeat(120))).result(), Some(&float(&"1".repeat(99))));
+}
+
+#[test]
+fn solve_again() {
+    // X=4, SHIFT SOLVE from the solution and from L–R goes back to the prompt
+    let solved = "Alpha BracketEnd Alpha Fmla 4 Shift Fmla 1 Shift Fmla";
+    assert_eq!(run(solved).display_text(), "                  D\n X=\n                4");
+    assert_eq!(run(&format!("{solved} Shift Fmla")).display_text(), "                  D\n X?\n                4");
+    assert_eq!(run(&format!("{solved} Exe Shift Fmla")).display_text(), "                  D\n X?\n                4");
+}
+
+#[test]
+fn series() {
+    // Σ(X,X,1,100), Π(X,X,1,5)
+    let sum = run("Shift BracketStart Alpha BracketEnd Comma Alpha BracketEnd Comma 1 Comma 1 0 0 BracketEnd Exe");
+    assert_eq!(sum.result(), Some(&float("5050")));
+    assert_eq!(sum.variable(Var::X), &float("0"));
+    let product = run("Shift BracketEnd Alpha BracketEnd Comma Alpha BracketEnd Comma 1 Comma 5 BracketEnd Exe");
+    assert_eq!(product.result(), Some(&float("120")));
+
+    // Σ(1,X,1,10000) is the most terms, Σ(1,X,0,10000) one too many
+    let most = run("Shift BracketStart 1 Comma Alpha BracketEnd Comma 1 Comma 1 0 0 0 0 BracketEnd Exe");
+    assert_eq!(most.result(), Some(&float("10000")));
+    let too_many = run("Shift BracketStart 1 Comma Alpha BracketEnd Comma 0 Comma 1 0 0 0 0 BracketEnd Exe");
+    assert_eq!(too_many.error(), Some(Error::Math));
+
+    // Σ(X,X,5,1), Σ(X,X,1,2.5)
+    let reversed = run("Shift BracketStart Alpha BracketEnd Comma Alpha BracketEnd Comma 5 Comma 1 BracketEnd Exe");
+    assert_eq!(reversed.error(), Some(Error::Math));
+    let fraction = run("Shift BracketStart Alpha BracketEnd Comma Alpha BracketEnd Comma 1 Comma 2 Dot 5 BracketEnd Exe");
+    assert_eq!(fraction.error(), Some(Error::Math));
+}
+
+#[test]
+fn random() {
+    let seeded = |seed, script| {
+        let mut calc = Calculator::with_seed(seed);
+        calc.run_keys(&parse_script(script).unwrap());
+        calc.result().unwrap().clone()
+    };
+
+    // Ran#
+    let ran = seeded(7, "Shift Dot Exe");
+    assert_eq!(ran, seeded(7, "Shift Dot Exe"));
+    assert_ne!(ran, seeded(8, "Shift Dot Exe"));
+    assert!(ran >= float("0") && ran < float("1"));
+    // three decimal places
+    assert!((ran.clone() * float("1000")).unwrap().to_i64().is_some());
+
+    // RanInt#(1,6)
+    let dice = seeded(7, "Alpha Dot 1 Comma 6 BracketEnd Exe");
+    assert_eq!(dice, seeded(7, "Alpha Dot 1 Comma 6 BracketEnd Exe"));
+    assert!(dice.to_i64().is_some_and(|i| (1..=6).contains(&i)));
+}
+
+#[test]
+fn coordinates() {
+    // Pol is SHIFT +, Rec is SHIFT –, both giving X and Y on two lines
+    let pol = run("Shift Add 1 Comma 1 BracketEnd Exe");
+    assert_eq!(pol.display_text(), "                  D\n r=1.414213562\nθ=             45");
+    assert_eq!(pol.variable(Var::X), &float("1.414213562373095"));
+    assert_eq!(pol.variable(Var::Y), &float("45"));
+    assert_eq!(pol.answer(), pol.variable(Var::X));
+
+    let rec = run("Shift Subtract 2 Comma 9 0 BracketEnd Exe");
+    assert_eq!(rec.display_text(), "                  D\n x=0\ny=              2");
+    assert_eq!(rec.variable(Var::X), &float("0"));
+    assert_eq!(rec.variable(Var::Y), &float("2"));
+
+    // the angle follows the unit in SETUP
+    assert_eq!(
+        run("Shift Mode 2 Shift Add 1 Comma 1 BracketEnd Exe").display_text(),
+        "                  R\n r=1.414213562\nθ=   0.7853981634",
+    );
+    assert_eq!(
+        run("Shift Mode 3 Shift Subtract 2 Comma 1 0 0 BracketEnd Exe").display_text(),
+        "                  G\n x=0\ny=              2",
+    );
+}
+
+#[test]
+fn constants() {
+    // CONST is SHIFT 7, four constants a page
+    assert_eq!(run("Shift 7").display_text(), "                  D\n← mₚ  mₙ  mₑ  mμ →\n  1    2    3    4");
+    assert_eq!(run("Shift 7 Right").display_text(), "                  D\n← a₀  h  μN  μB  →\n  1    2    3    4");
+    // left of the first page is the last one
+    assert_eq!(run("Shift 7 Left").display_text(), "                  D\n← G  atm         →\n  1        2");
+
+    let proton = run("Shift 7 1 Exe");
+    assert_eq!(proton.display_text(), "                  D\n mₚ\n1.672621924×10⁻²⁷");
+    assert_eq!(proton.result(), Some(&float("1.67262192369e-27")));
+    assert_eq!(run("Shift 7 Right 2 Exe").result(), Some(&float("6.62607015e-34")));
+    assert_eq!(run("Shift 7 Left 2 Exe").result(), Some(&float("101325")));
+
+    // a constant after a number multiplies it
+    assert_eq!(run("2 Shift 7 Right Right 1 Exe").display_text(), "                  D\n 2ħ\n2.109143634×10⁻³⁴");
+}
+
+#[test]
+fn conversions() {
+    // CONV is SHIFT 8, a pair of directions a page
+    assert_eq!(run("Shift 8").display_text(), "                  D\n← in▶cm  cm▶in  →\n  1        2");
+    assert_eq!(run("Shift 8 Right").display_text(), "                  D\n← ft▶m  m▶ft    →\n  1        2");
+    assert_eq!(run("Shift 8 Left").display_text(), "                  D\n← °C▶°F  °F▶°C  →\n  1        2");
+
+    assert_eq!(run("1 Shift 8 1 Exe").display_text(), "                  D\n 1in▶cm\n             2.54");
+    assert_eq!(run("3 Shift 8 Right 1 Exe").result(), Some(&float("0.9144")));
+    assert_eq!(run("1 0 0 Shift 8 Left 1 Exe").result(), Some(&float("212")));
+    // a conversion needs a value before it
+    assert_eq!(run("Shift 8 2 Exe").error(), Some(Error::Syntax));
+
+    // right after a result it converts the answer
+    assert_eq!(run("2 5 4 Exe Shift 8 2 Exe").display_text(), "                  D\n Anscm▶in\n              100");
+}
+
+#[test]
+fn regression() {
+    // y = 2x + 1 in REG Lin, each pair entered with DT
+    let data = "Mode 5 1 1 Comma 3 MPlus 2 Comma 5 MPlus 3 Comma 7 MPlus";
+    let calc = run(data);
+    assert_eq!(calc.mode(), Mode::PairedStat(Regression::Linear));
+    assert_eq!(calc.display_text(), "       REG        D\n n=\n                3");
+
+    // A, B and r from S-VAR, then 4ŷ and 9x̂
+    assert_eq!(run(&format!("{data} Shift 2 1 Exe")).result(), Some(&float("1")));
+    assert_eq!(run(&format!("{data} Shift 2 2 Exe")).result(), Some(&float("2")));
+    assert_eq!(run(&format!("{data} Shift 2 Right 1 Exe")).result(), Some(&float("1")));
+    assert_eq!(run(&format!("{data} 4 Shift 2 Right 3 Exe")).display_text(), "       REG        D\n 4ŷ\n                9");
+    assert_eq!(run(&format!("{data} 9 Shift 2 Right 2 Exe")).result(), Some(&float("4")));
+
+    // CL takes out a pair that was entered, one that was not is left alone
+    assert_eq!(run(&format!("{data} 3 Comma 7 Shift MPlus")).result(), Some(&float("2")));
+    assert_eq!(run(&format!("{data} 9 Comma 9 Shift MPlus")).result(), Some(&float("3")));
+    assert_eq!(run(&format!("{data} 1 MPlus")).error(), Some(Error::Syntax));
+    // a third number is the frequency, only with it on
+    assert_eq!(run(&format!("{data} 1 Comma 3 Comma 2 MPlus")).error(), Some(Error::Syntax));
+
+    // y = x² + 1 in REG Quad, 5x̂₁ and 5x̂₂ are its two roots
+    let quadratic = "Mode 5 6 0 Comma 1 MPlus 1 Comma 2 MPlus 2 Comma 5 MPlus 3 Comma 1 0 MPlus";
+    assert_eq!(run(&format!("{quadratic} 5 Shift 2 Right 1 Exe")).result(), Some(&float("2")));
+    assert_eq!(run(&format!("{quadratic} 5 Shift 2 Right 2 Exe")).result(), Some(&float("-2")));
+    assert_eq!(run(&format!("{quadratic} 4 Shift 2 Right 3 Exe")).result(), Some(&float("17")));
+}
+
+/// Types `keys` into P1 as a COMP program, then runs it and presses `then`.
+fn program(keys: &str, then: &str) -> Calculator {
+    run(&format!("Mode 6 1 1 {keys} Ac Mode 1 Prog 1 {then}"))
+}
+
+#[test]
+fn programs() {
+    // PROG 2 is →, 3 is :, 4 is ◢, A is ALPHA (-) and B is ALPHA °’”
+    let prompt = program("Prog 1 Prog 2 Alpha Negative Prog 3 Alpha Negative Multiply 2 Prog 4", "");
+    assert_eq!(prompt.display_text(), "                  D\n A?\n                0");
+    let paused = program("Prog 1 Prog 2 Alpha Negative Prog 3 Alpha Negative Multiply 2 Prog 4", "5 Exe");
+    assert_eq!(paused.display_text(), "                  D\n Disp\n               10");
+    assert_eq!(paused.variable(Var::A), &float("5"));
+    let ended = program("Prog 1 Prog 2 Alpha Negative Prog 3 Alpha Negative Multiply 2 Prog 4", "5 Exe Exe");
+    assert_eq!(ended.display_text(), "                  D\n\n               10");
+
+    // 0→A:Lbl 1:A+1→A:A<3⇒Goto 1:A
+    let looped = program(
+        "0 Prog 2 Alpha Negative Prog 3 Prog Right 2 1 Prog 3 Alpha Negative Add 1 Prog 2 Alpha Negative Prog 3 \
+         Alpha Negative Prog Left 1 3 Prog Right 1 Prog Right 3 1 Prog 3 Alpha Negative",
+        "",
+    );
+    assert_eq!(looped.result(), Some(&float("3")));
+    // Goto 2 without a Lbl 2
+    assert_eq!(program("Prog Right 3 2", "").error(), Some(Error::Go));
+}
+
+#[test]
+fn program_conditions() {
+    // 5→A:A>9⇒1→A:A skips 1→A, with A<9 it does not
+    let skipped = program(
+        "5 Prog 2 Alpha Negative Prog 3 Alpha Negative Prog Right Right Right 3 9 \
+         Prog Right 1 1 Prog 2 Alpha Negative Prog 3 Alpha Negative",
+        "",
+    );
+    assert_eq!(skipped.result(), Some(&float("5")));
+    let taken = program(
+        "5 Prog 2 Alpha Negative Prog 3 Alpha Negative Prog Left 1 9 \
+         Prog Right 1 1 Prog 2 Alpha Negative Prog 3 Alpha Negative",
+        "",
+    );
+    assert_eq!(taken.result(), Some(&float("1")));
+
+    // 0→B:-1→A:Isz A:5→B:B skips 5→B as A reaches 0
+    let isz = program(
+        "0 Prog 2 Alpha Base60 Prog 3 Negative 1 Prog 2 Alpha Negative Prog 3 \
+         Prog Right Right 1 Alpha Negative Prog 3 5 Prog 2 Alpha Base60 Prog 3 Alpha Base60",
+        "",
+    );
+    assert_eq!(isz.result(), Some(&float("0")));
+    assert_eq!(isz.variable(Var::A), &float("0"));
+    // Dsz from 1 skips, from 5 does not
+    let dsz = |a| program(&format!(
+        "0 Prog 2 Alpha Base60 Prog 3 {a} Prog 2 Alpha Negative Prog 3 \
+         Prog Right Right 2 Alpha Negative Prog 3 5 Prog 2 Alpha Base60 Prog 3 Alpha Base60",
+    ), "");
+    assert_eq!(dsz(1).result(), Some(&float("0")));
+    assert_eq!(dsz(5).result(), Some(&float("5")));
+    assert_eq!(dsz(5).variable(Var::A), &float("4"));
+}
+
+#[test]
+fn program_interrupted() {
+    // Lbl 0:Goto 0 runs a few statements each tick, keys other than AC wait
+    let endless = "Prog Right 2 0 Prog 3 Prog Right 3 0";
+    assert_eq!(program(endless, "2 Add 3 Exe").result(), None);
+    assert_eq!(program(endless, "Ac 2 Add 3 Exe").result(), Some(&float("5")));
+}
+
+#[test]
+fn program_steps() {
+    // the four areas share 680 steps, each shown the ones left
+    assert_eq!(run("Mode 6").display_text(), "       PROG       D\n  P1  P2  P3  P4\n  1    2    3    4");
+    assert_eq!(run("Mode 6 1 1").display_text(), "       PROG       D\n\n  P1           680");
+    let first = format!("Mode 6 1 1 {}Ac", "1 ".repeat(600));
+    assert_eq!(run(&format!("{first} 2 1")).display_text(), "       PROG       D\n\n  P2            80");
+
+    // 100 keys into P2, the last 20 are refused
+    let second = format!("{first} 2 1 {}", "2 ".repeat(100));
+    assert_eq!(run(&second).display_text(), format!("       PROG       D\n◀{}\n  P2             0", "2".repeat(15)));
+    assert_eq!(run(&format!("{second} 3")).display_text(), run(&second).display_text());
+    assert_eq!(run(&format!("{second} Ac Mode 1 Prog 2")).result(), Some(&float(&"2".repeat(80))));
+    assert_eq!(run(&format!("{second} Ac 1 1")).display_text(), format!("       PROG       D\n◀{}\n  P1             0", "1".repeat(15)));
+    // a step deleted from P1 is one more for any area
+    assert_eq!(run(&format!("{second} Ac 1 1 Del Ac 3 1")).display_text(), "       PROG       D\n\n  P3             1");
+}
+
+#[test]
+fn formulas() {
+    // Pythagoras, prompting for a and b
+    assert_eq!(run("Fmla").display_text(), "                  D\n← Pythagoras    →\n  1");
+    assert_eq!(run("Fmla 1").display_text(), "                  D\n a?\n                0");
+    assert_eq!(run("Fmla 1 3 Exe").display_text(), "                  D\n b?\n                0");
+    let solved = run("Fmla 1 3 Exe 4 Exe");
+    assert_eq!(solved.display_text(), "                  D\n c=\n                5");
+    assert_eq!(solved.answer(), &float("5"));
+    // EXE asks again, keeping the values given
+    assert_eq!(run("Fmla 1 3 Exe 4 Exe Exe").display_text(), "                  D\n a?\n                3");
+    assert_eq!(run("Fmla 1 3 Exe 4 Exe Exe Exe Exe").result(), Some(&float("5")));
+}
+
+#[test]
+fn user_formulas() {
+    // Y=2×X on the page after the built-in ones
+    let new = "Fmla Left 1 Alpha Comma Exe 2 Multiply Alpha BracketEnd Exe";
+    assert_eq!(run("Fmla Left").display_text(), "                  D\n← NEW            →\n  1");
+    assert_eq!(run(new).display_text(), "                  D\n← Y  Edt  Del    →\n  1     2     3");
+    assert_eq!(run(&format!("{new} 1")).display_text(), "                  D\n X?\n                0");
+    let solved = run(&format!("{new} 1 3 Exe"));
+    assert_eq!(solved.display_text(), "                  D\n Y=\n                6");
+    assert_eq!(solved.variable(Var::X), &float("3"));
+
+    // editing keeps the name and changes the body to 3
+    assert_eq!(run(&format!("{new} 2")).display_text(), "                  D\n Y\n  Name?");
+    assert_eq!(run(&format!("{new} 2 Exe")).display_text(), "                  D\n 2×X\n  Formula?");
+    assert_eq!(run(&format!("{new} 2 Exe Del Del Del 3 Exe 1 Exe")).result(), Some(&float("3")));
+
+    // deleting it leaves NEW in its place
+    assert_eq!(run(&format!("{new} 3")).display_text(), "                  D\n← NEW            →\n  1");
+    // names are six tokens at most
+    assert_eq!(run("Fmla Left 1 1 2 3 4 5 6 7 Exe").error(), Some(Error::Syntax));
+
+    // four formulas A to D, after them there is no NEW
+    let four: String = ["Negative", "Base60", "Hyp", "Sin"].iter()
+        .map(|v| format!("Fmla Left 1 Alpha {v} Exe 1 Exe Ac "))
+        .collect();
+    assert_eq!(run(&format!("{four}Fmla Left")).display_text(), "                  D\n← D  Edt  Del    →\n  1     2     3");
+    assert_eq!(run(&format!("{four}Fmla Left Left Left Left")).display_text(), "                  D\n← A  Edt  Del    →\n  1     2     3");
+    assert_eq!(run(&format!("{four}Fmla Left 3")).display_text(), "                  D\n← NEW            →\n  1");
+}
+
+#[test]
+fn calc() {
+    // A×2, CALC is SHIFT PROG
+    let calc = "Alpha Negative Multiply 2 Shift Prog";
+    assert_eq!(run(calc).display_text(), "                  D\n A?\n                0");
+    let once = run(&format!("{calc} 3 Exe"));
+    assert_eq!(once.display_text(), "                  D\n A×2\n                6");
+    assert_eq!(once.variable(Var::A), &float("3"));
+
+    // EXE prompts again with the last value, kept when nothing is typed
+    assert_eq!(run(&format!("{calc} 3 Exe Exe")).display_text(), "                  D\n A?\n                3");
+    assert_eq!(run(&format!("{calc} 3 Exe Exe Exe")).result(), Some(&float("6")));
+    assert_eq!(run(&format!("{calc} 3 Exe Exe 5 Exe")).result(), Some(&float("10")));
+
+    // AC goes back to the expression, which EXE then calculates as usual
+    assert_eq!(run(&format!("{calc} 3 Exe Ac")).display_text(), "                  D\n A×2\n");
+    assert_eq!(run(&format!("{calc} 3 Exe Ac Exe")).result(), Some(&float("6")));
+
+    // each variable in turn
+    let two = "Alpha Negative Multiply Alpha Base60 Shift Prog 2 Exe";
+    assert_eq!(run(two).display_text(), "                  D\n B?\n                0");
+    assert_eq!(run(&format!("{two} 3 Exe")).result(), Some(&float("6")));
+}